@@ -0,0 +1,47 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::page::{parse_page, Page};
+use crate::service::{self, resolve_repository, Service};
+use sha2::{Digest, Sha256};
+
+// Top-level directory of the archive generated by each service. The checksum of a local archive
+// can match the downloaded one only when the directory name is the same
+fn archive_prefix(cfg: &Config, name: &str, tag: bool, commit: &str) -> Result<String> {
+    let repo = resolve_repository(cfg)?;
+    Ok(match repo.service {
+        // GitHub strips 'v' prefix of version tags like 'v1.2.3'
+        Service::GitHub { .. } if tag => {
+            let name = match name.strip_prefix('v') {
+                Some(n) if n.starts_with(|c: char| c.is_ascii_digit()) => n,
+                _ => name,
+            };
+            format!("{}-{}/", repo.repo, name)
+        }
+        Service::GitHub { .. } => format!("{}-{}/", repo.repo, commit),
+        Service::GitLab if tag => {
+            format!("{}-{}-{}/", repo.repo, name.replace('/', "-"), commit)
+        }
+        Service::GitLab => format!("{}-{}/", repo.repo, commit),
+        Service::Bitbucket => format!("{}-{}-{}/", repo.user, repo.repo, &commit[..12]),
+        Service::AzureDevOps => "".to_string(),
+    })
+}
+
+// Returns a line in the same format as `sha256sum` so that the output can be pasted into build
+// recipes. Note that the checksum may differ from the archive served by the service when its
+// compression is not byte-identical to `git archive`
+pub fn checksum(cfg: &Config) -> Result<String> {
+    let page = parse_page(cfg)?;
+    let url = service::build_page_url(&page, cfg)?;
+    let (name, tag, format) = match page {
+        Page::Archive { name, tag, format } => (name, tag, format),
+        p => unreachable!("Unexpected page for archive: {:?}", p),
+    };
+
+    let git = cfg.git();
+    let commit = git.hash(format!("{}^{{commit}}", name))?;
+    let prefix = archive_prefix(cfg, &name, tag, &commit)?;
+    let bytes = git.archive(&name, format.extension(), prefix)?;
+
+    Ok(format!("{:x}  {}", Sha256::digest(&bytes), url))
+}
@@ -1,8 +1,13 @@
 use crate::async_runtime;
-use crate::config::{Config, EnvConfig};
+use crate::config::{
+    Config, EnvConfig, IssueOptions, LogOptions, PullRequestOptions, SearchOptions,
+};
+use crate::dependency;
 use crate::error::{Error, ErrorKind, Result};
 use crate::git::Git;
 use crate::github_api::Client;
+use crate::page::{split_qualified_reference, ArchiveFormat, Section, WebIde};
+use crate::upstream;
 use getopts::Options;
 use std::env;
 use std::ffi::OsStr;
@@ -35,12 +40,24 @@ fn fix_ssh_url(mut url: String) -> String {
     url
 }
 
-#[cfg_attr(feature = "cargo-clippy", allow(clippy::large_enum_variant))]
 #[derive(Debug)]
 pub enum Parsed {
     Help(String),
     Version(&'static str),
-    OpenPage(Config),
+    OpenPage(Box<Config>),
+}
+
+// Reference qualified with repository like owner/repo#12 can be opened outside a Git repository.
+// The repository is resolved on github.com
+fn qualified_repo_outside_git_repo(args: &[String], git: &Git) -> Option<String> {
+    let (user, repo, _) = match args {
+        [arg] => split_qualified_reference(arg)?,
+        _ => return None,
+    };
+    if git.root_dir().is_ok() {
+        return None;
+    }
+    Some(format!("https://github.com/{}/{}.git", user, repo))
 }
 
 fn normalize_repo_format(mut slug: String, env: &EnvConfig) -> Result<String> {
@@ -83,6 +100,25 @@ fn get_cwd(specified: Option<String>) -> Result<PathBuf> {
     }
 }
 
+// Options to select the kind of page to open. Options in the same group can be specified together
+const PAGE_OPTIONS: &[&[&str]] = &[
+    &["--pr"],
+    &["--website"],
+    &["--section"],
+    &["--compare-upstream"],
+    &["--ci"],
+    &["--released-in"],
+    &["--package", "--docs"],
+    &["--archive"],
+    &["--log"],
+    &["--raw"],
+    &["--edit", "--web-ide"],
+    &["--search"],
+    &["--new-issue"],
+    &["--issues"],
+    &["--clone-url"],
+];
+
 const USAGE: &str = "\
 Usage: git brws [Options] {Args}
 
@@ -137,6 +173,18 @@ Examples:
 
     $ git brws --pr
 
+  - 'Create Pull Request' page with base branch and labels:
+
+    $ git brws --pr --base develop --label bug --label ui
+
+  - Line 30 of file in diff of pull request:
+
+    $ git brws --pr src/lib.rs#L30
+
+  - Summary of pull request in JSON:
+
+    $ git brws --pr --info --json
+
   - Website of repository at current directory
 
     $ git brws --website
@@ -147,7 +195,79 @@ Examples:
 
   - Issue page:
 
-    $ git brws '#8'";
+    $ git brws '#8'
+
+  - Issue search results:
+
+    $ git brws '#login crash'
+
+  - Issue page of other repository on the same host:
+
+    $ git brws 'rust-lang/rust#12345'
+
+  - Merge request, epic, milestone or snippet page in GitLab reference syntax:
+
+    $ git brws '!456'
+
+  - Issues referenced by current branch name and HEAD commit message:
+
+    $ git brws --issues
+
+  - 'New Issue' page with title, label and permalink to lines of file:
+
+    $ git brws --new-issue --title 'Crash on login' --label bug src/login.rs#L10-L20
+
+  - Code search in the repository:
+
+    $ git brws --search 'fn main' --lang rust
+
+  - Releases page of the repository:
+
+    $ git brws --section releases
+
+  - CI page of HEAD commit:
+
+    $ git brws --ci
+
+  - Release which shipped the commit:
+
+    $ git brws --released-in 0123abc
+
+  - Commit history of file by the author:
+
+    $ git brws --log --author rhysd src/page.rs
+
+  - Raw content of file:
+
+    $ git brws --raw scripts/install.sh
+
+  - Line of file in github.dev:
+
+    $ git brws --web-ide src/lib.rs#L10
+
+  - Lines of file on Sourcegraph:
+
+    $ git brws --sourcegraph src/lib.rs#L10-L20
+
+  - Source archive of the tag with its SHA-256 checksum:
+
+    $ git brws --archive --sha256 v1.2.3
+
+  - Clone URLs of the repository in JSON:
+
+    $ git brws --clone-url --json -r rhysd/git-brws
+
+  - Documentation of the package containing the file:
+
+    $ git brws --docs crates/core/src/lib.rs
+
+  - File of the dependency at the locked version:
+
+    $ git brws --dep serde src/lib.rs
+
+  - File in the upstream repository of the fork:
+
+    $ git brws --upstream src/lib.rs";
 
 impl Parsed {
     pub fn parse_iter<I>(argv: I) -> Result<Parsed>
@@ -179,7 +299,7 @@ impl Parsed {
         opts.optflag(
             "p",
             "pr",
-            "Open pull request page instead of repository page. If not existing, open 'Create Pull Request' page",
+            "Open pull request page instead of repository page. If not existing, open 'Create Pull Request' page. When file path is given, open the file in diff of the pull request",
         );
         opts.optflag(
             "w",
@@ -191,6 +311,174 @@ impl Parsed {
             "blame",
             "Open blame page instead of repository page. File path to blame must be passed also.",
         );
+        opts.optopt(
+            "",
+            "base",
+            "Base branch of a new pull request. Default value is the default branch of the repository",
+            "BRANCH",
+        );
+        opts.optflag(
+            "",
+            "draft",
+            "Create a new pull request as draft (only for GitLab)",
+        );
+        opts.optmulti(
+            "",
+            "label",
+            "Label to add to a new pull request or issue. This option can be specified multiple times",
+            "LABEL",
+        );
+        opts.optmulti(
+            "",
+            "reviewer",
+            "User to request a review of a new pull request (only for GitLab). This option can be specified multiple times",
+            "USER",
+        );
+        opts.optmulti(
+            "",
+            "assignee",
+            "User to assign to a new pull request or issue. This option can be specified multiple times",
+            "USER",
+        );
+        opts.optopt(
+            "",
+            "template",
+            "Template file name for a new pull request or issue",
+            "NAME",
+        );
+        opts.optflag(
+            "",
+            "info",
+            "Print number, title, state, author, branches, review decision and checks of the pull request to stdout instead of opening it. Exit status is 1 when no pull request is found. --pr must be specified also",
+        );
+        opts.optflag("", "json", "Print information in JSON format");
+        opts.optflag(
+            "",
+            "issues",
+            "Open issues referenced by the branch name and the commit message. Revision of the commit can be given as argument. Default value is HEAD",
+        );
+        opts.optflag(
+            "",
+            "new-issue",
+            "Open 'New Issue' page. When file path (and line) is given, permalink to it and current commit hash are added to the body",
+        );
+        opts.optopt("", "title", "Title of a new issue", "TEXT");
+        opts.optopt("", "body", "Body of a new issue", "TEXT");
+        opts.optopt(
+            "",
+            "search",
+            "Open code search results of the repository. Branch specified with --branch is searched. Sourcegraph is used when $GIT_BRWS_SOURCEGRAPH_URL is set",
+            "QUERY",
+        );
+        opts.optopt(
+            "",
+            "path",
+            "File path to filter code search results",
+            "PATH",
+        );
+        opts.optopt(
+            "",
+            "lang",
+            "Programming language to filter code search results",
+            "LANG",
+        );
+        opts.optopt(
+            "",
+            "section",
+            "Open a section of the repository: releases, tags, branches, ci, wiki, pulls, issues, milestones, labels, security, insights, forks or settings",
+            "NAME",
+        );
+        opts.optflag(
+            "",
+            "ci",
+            "Open CI page of the commit. Revision of the commit can be given as argument. Default value is HEAD. On GitHub, the failed run is opened directly when API token is available",
+        );
+        opts.optflag(
+            "",
+            "released-in",
+            "Open release page of the earliest tag which contains the commit. Revision of the commit can be given as argument. Default value is HEAD",
+        );
+        opts.optflag(
+            "",
+            "log",
+            "Open commit history page. When file or directory path (and revision) is given, history of the path is opened. When revision is given, history of the revision is opened",
+        );
+        opts.optopt("", "author", "Author to filter commit history", "USER");
+        opts.optopt(
+            "",
+            "since",
+            "Show commits more recent than the date in commit history (e.g. 2020-01-31)",
+            "DATE",
+        );
+        opts.optopt(
+            "",
+            "until",
+            "Show commits older than the date in commit history (e.g. 2020-01-31)",
+            "DATE",
+        );
+        opts.optflag(
+            "",
+            "raw",
+            "Open raw content of the file instead of file page. Media URL is opened for a file tracked by Git LFS. File path to open must be passed also",
+        );
+        opts.optflag(
+            "",
+            "edit",
+            "Open edit page of the file on the branch. File path to edit must be passed also",
+        );
+        opts.optflagopt(
+            "",
+            "web-ide",
+            "Open the file (and line) in web IDE: github.dev, vscode.dev, gitpod or codespaces. Default value is github.dev",
+            "IDE",
+        );
+        opts.optflag(
+            "",
+            "sourcegraph",
+            "Open repository, file, directory, commit or diff on Sourcegraph instead of the hosting service. Instance URL can be set with $GIT_BRWS_SOURCEGRAPH_URL. Default value is https://sourcegraph.com",
+        );
+        opts.optflagopt(
+            "",
+            "archive",
+            "Open URL to download source archive of the tag or the commit. Format is 'tar.gz' (default) or 'zip'. HEAD is used when no argument is given",
+            "FORMAT",
+        );
+        opts.optflag(
+            "",
+            "sha256",
+            "Print SHA-256 checksum of the source archive built locally with `git archive` followed by its URL. --archive is required",
+        );
+        opts.optflag(
+            "",
+            "clone-url",
+            "Print HTTPS, SSH (scp-like) and ssh:// clone URLs of the repository. Output is in JSON format with --json",
+        );
+        opts.optflag(
+            "",
+            "package",
+            "Open the package page on its registry (crates.io, npm, PyPI, pkg.go.dev or RubyGems). Manifest is searched from the directory of the path argument (or current directory) up to the repository root",
+        );
+        opts.optflag(
+            "",
+            "docs",
+            "Open the documentation page of the package (docs.rs and so on) instead of the registry page",
+        );
+        opts.optopt(
+            "",
+            "dep",
+            "Open repository of the dependency locked in Cargo.lock or package-lock.json at the locked version. Repository URL is read from the manifest cached locally by cargo or npm. Arguments are treated as paths in the dependency",
+            "NAME",
+        );
+        opts.optflag(
+            "",
+            "upstream",
+            "Open the page in the upstream repository of the fork. Upstream is resolved via GitHub API or 'upstream' remote",
+        );
+        opts.optflag(
+            "",
+            "compare-upstream",
+            "Open the page to compare the branch of the fork against the default branch of its upstream. Branch name can be given as argument",
+        );
         opts.optflag("h", "help", "Print this help");
         opts.optflag("v", "version", "Show version");
 
@@ -206,33 +494,123 @@ impl Parsed {
             ));
         }
 
-        let env = EnvConfig::from_iter(env::vars())?.with_global_env();
-        let cwd = get_cwd(matches.opt_str("d"))?;
-        let branch = matches.opt_str("b");
-        let (repo_url, remote) = match (matches.opt_str("r"), matches.opt_str("R")) {
-            (Some(repo), remote) => {
-                if !matches.free.is_empty() {
-                    return Error::err(ErrorKind::ArgsNotAllowed {
-                        flag: "--repo {repo}",
-                        args: matches.free,
+        if matches.opt_present("info") && !matches.opt_present("p") {
+            return Error::err(ErrorKind::MissingRequiredOption {
+                option: "--info",
+                required: "--pr",
+            });
+        }
+
+        // Note: Kinds of page are exclusive. Options in the same group select the same kind
+        let mut page_option = None;
+        for group in PAGE_OPTIONS {
+            if let Some(&flag) = group.iter().find(|f| matches.opt_present(&f[2..])) {
+                if let Some(option) = page_option {
+                    return Error::err(ErrorKind::ConflictingOptions {
+                        option,
+                        conflicting: flag,
                     });
                 }
-                (normalize_repo_format(repo, &env)?, remote)
+                page_option = Some(flag);
             }
-            (None, remote) => {
-                let git = Git::new(&cwd, &env.git_command);
-                let (url, remote) = if let Some(remote) = remote {
-                    (git.remote_url(&remote)?, remote)
-                } else {
-                    git.tracking_remote_url(&branch)?
-                };
-                (url, Some(remote))
+        }
+
+        let section = match matches.opt_str("section") {
+            Some(name) => Some(name.parse::<Section>()?),
+            None => None,
+        };
+
+        let web_ide = if matches.opt_present("web-ide") {
+            match matches.opt_str("web-ide") {
+                Some(name) => Some(name.parse::<WebIde>()?),
+                None => Some(WebIde::GitHubDev),
+            }
+        } else {
+            None
+        };
+
+        if matches.opt_present("sha256") && !matches.opt_present("archive") {
+            return Error::err(ErrorKind::MissingRequiredOption {
+                option: "--sha256",
+                required: "--archive",
+            });
+        }
+
+        // Note: Repository of the dependency is resolved from lockfile so it cannot be specified
+        if matches.opt_present("dep") {
+            for &(name, flag) in &[("r", "--repo"), ("R", "--remote")] {
+                if matches.opt_present(name) {
+                    return Error::err(ErrorKind::ConflictingOptions {
+                        option: "--dep",
+                        conflicting: flag,
+                    });
+                }
+            }
+        }
+
+        let archive = if matches.opt_present("archive") {
+            match matches.opt_str("archive") {
+                Some(name) => Some(name.parse::<ArchiveFormat>()?),
+                None => Some(ArchiveFormat::TarGz),
             }
+        } else {
+            None
+        };
+
+        let env = EnvConfig::from_iter(env::vars())?.with_global_env();
+        let cwd = get_cwd(matches.opt_str("d"))?;
+        let dependency = match matches.opt_str("dep") {
+            Some(name) => Some(dependency::resolve(&name, &cwd, &env)?),
+            None => None,
         };
+        // Note: Revision of the dependency is used as branch so that the locked version is opened
+        let branch = matches
+            .opt_str("b")
+            .or_else(|| dependency.as_ref().map(|d| d.revision.clone()));
+        let (repo_url, remote, local_checkout) =
+            match (matches.opt_str("r"), matches.opt_str("R"), &dependency) {
+                (_, _, Some(dep)) => (dep.repo_url.clone(), None, false),
+                (Some(repo), remote, None) => {
+                    if !matches.free.is_empty() {
+                        return Error::err(ErrorKind::ArgsNotAllowed {
+                            flag: "--repo {repo}",
+                            args: matches.free,
+                        });
+                    }
+                    (normalize_repo_format(repo, &env)?, remote, false)
+                }
+                (None, remote, None) => {
+                    let git = Git::new(&cwd, &env.git_command);
+                    match (remote, qualified_repo_outside_git_repo(&matches.free, &git)) {
+                        (None, Some(url)) => (url, None, false),
+                        (Some(remote), _) => (git.remote_url(&remote)?, Some(remote), false),
+                        (None, None) => {
+                            let (url, remote) = git.tracking_remote_url(&branch)?;
+                            (url, Some(remote), true)
+                        }
+                    }
+                }
+            };
 
         let repo_url = fix_ssh_url(repo_url);
 
-        Ok(Parsed::OpenPage(Config {
+        let compare_upstream = matches.opt_present("compare-upstream");
+        // Note: Remote is retargeted at the upstream since the local checkout is the fork
+        let (repo_url, remote, local_checkout, fork) =
+            if compare_upstream || matches.opt_present("upstream") {
+                let git = Git::new(&cwd, &env.git_command);
+                let upstream = upstream::resolve(&repo_url, &git, &env)?;
+                (
+                    fix_ssh_url(upstream.url),
+                    upstream.remote,
+                    false,
+                    Some(upstream.fork),
+                )
+            } else {
+                (repo_url, remote, local_checkout, None)
+            };
+
+        Ok(Parsed::OpenPage(Box::new(Config {
             repo_url,
             branch,
             cwd,
@@ -240,9 +618,55 @@ impl Parsed {
             pull_request: matches.opt_present("p"),
             website: matches.opt_present("w"),
             blame: matches.opt_present("B"),
+            pr_options: PullRequestOptions {
+                base: matches.opt_str("base"),
+                draft: matches.opt_present("draft"),
+                labels: matches.opt_strs("label"),
+                reviewers: matches.opt_strs("reviewer"),
+                assignees: matches.opt_strs("assignee"),
+                template: matches.opt_str("template"),
+            },
+            info: matches.opt_present("info"),
+            json: matches.opt_present("json"),
+            issues: matches.opt_present("issues"),
+            new_issue: matches.opt_present("new-issue"),
+            issue_options: IssueOptions {
+                title: matches.opt_str("title"),
+                body: matches.opt_str("body"),
+                labels: matches.opt_strs("label"),
+                assignees: matches.opt_strs("assignee"),
+                template: matches.opt_str("template"),
+            },
+            search: matches.opt_str("search"),
+            search_options: SearchOptions {
+                path: matches.opt_str("path"),
+                language: matches.opt_str("lang"),
+            },
+            section,
+            ci: matches.opt_present("ci"),
+            released_in: matches.opt_present("released-in"),
+            log: matches.opt_present("log"),
+            raw: matches.opt_present("raw"),
+            edit: matches.opt_present("edit"),
+            web_ide,
+            sourcegraph: matches.opt_present("sourcegraph"),
+            archive,
+            sha256: matches.opt_present("sha256"),
+            clone_url: matches.opt_present("clone-url"),
+            package: matches.opt_present("package"),
+            docs: matches.opt_present("docs"),
+            dependency,
+            fork,
+            compare_upstream,
+            log_options: LogOptions {
+                author: matches.opt_str("author"),
+                since: matches.opt_str("since"),
+                until: matches.opt_str("until"),
+            },
             args: matches.free,
             remote,
+            local_checkout,
             env,
-        }))
+        })))
     }
 }
@@ -0,0 +1,59 @@
+use crate::config::EnvConfig;
+use crate::error::{Error, ErrorKind, Result};
+use reqwest::{Client as ReqwestClient, Proxy, StatusCode};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    website: Option<String>,
+}
+
+// 'website' field of repository in response of repositories API. Empty string means it is not set
+pub fn website_from_json(json: &str) -> Option<String> {
+    let repo: Repository = serde_json::from_str(json).ok()?;
+    repo.website.filter(|w| !w.is_empty())
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    token: Option<&'a str>,
+}
+
+impl<'a> Client<'a> {
+    pub fn build(env: &'a EnvConfig) -> Result<Self> {
+        let mut b = ReqwestClient::builder().user_agent("git-brws");
+
+        if let Some(ref p) = env.https_proxy {
+            if !p.is_empty() {
+                b = b.proxy(Proxy::https(p)?);
+            }
+        }
+
+        Ok(Self {
+            client: b.build()?,
+            token: env.bitbucket_token.as_deref(),
+        })
+    }
+
+    pub async fn website(&self, user: &str, repo: &str) -> Result<Option<String>> {
+        let url = format!(
+            "https://api.bitbucket.org/2.0/repositories/{}/{}",
+            user, repo
+        );
+        let mut req = self.client.get(url.as_str());
+        if let Some(token) = self.token {
+            req = req.bearer_auth(token);
+        }
+
+        let res = req.send().await?;
+        let status = res.status();
+        if status != StatusCode::OK {
+            return Error::err(ErrorKind::BitbucketStatusFailure {
+                status,
+                msg: res.text().await.unwrap(),
+            });
+        }
+
+        Ok(website_from_json(&res.text().await?))
+    }
+}
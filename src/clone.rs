@@ -0,0 +1,82 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::service::{parse_repo_url, resolve_repository, Service};
+use serde_derive::Serialize;
+use url::Url;
+
+// Clone URLs printed with --clone-url
+#[derive(PartialEq, Debug, Serialize)]
+pub struct CloneUrls {
+    pub https: String,
+    pub ssh: String,
+    pub ssh_url: String,
+}
+
+// SSH port of the remote URL. Default port is omitted
+fn ssh_port(cfg: &Config) -> Result<Option<u16>> {
+    let url = parse_repo_url(&cfg.repo_url)?;
+    if url.scheme() != "ssh" {
+        return Ok(None);
+    }
+    Ok(url.port().filter(|p| *p != 22))
+}
+
+// Organization, project and repository names of Azure DevOps remote URL. The path is
+// '{org}/{project}/_git/{repo}' or 'v3/{org}/{project}/{repo}' for SSH. Project is omitted as
+// '{org}/_git/{repo}' when it is named after the repository
+fn azure_devops_names(url: &Url) -> Option<(&str, &str, &str)> {
+    let segments: Vec<_> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    match segments.as_slice() {
+        ["v3", org, project, repo] | [org, project, "_git", repo] => Some((org, project, repo)),
+        [org, "_git", repo] => Some((org, repo, repo)),
+        _ => None,
+    }
+}
+
+// HTTPS URL uses the same host as pages opened by git-brws, including port number mapped with
+// $GIT_BRWS_GHE_SSH_PORT or $GIT_BRWS_GITLAB_SSH_PORT. SCP-like syntax cannot have port number so
+// only ssh:// URL preserves the port of the SSH remote
+pub fn clone_urls(cfg: &Config) -> Result<CloneUrls> {
+    let repo = resolve_repository(cfg)?;
+    let port = match ssh_port(cfg)? {
+        Some(p) => format!(":{}", p),
+        None => "".to_string(),
+    };
+
+    if repo.service == Service::AzureDevOps {
+        let url = parse_repo_url(&cfg.repo_url)?;
+        let (org, project, name) = azure_devops_names(&url).unwrap_or((
+            repo.user.as_str(),
+            repo.repo.as_str(),
+            repo.repo.as_str(),
+        ));
+        let path = format!("{}/{}/{}", org, project, name);
+        return Ok(CloneUrls {
+            https: format!("https://dev.azure.com/{}/{}/_git/{}", org, project, name),
+            ssh: format!("git@ssh.dev.azure.com:v3/{}", path),
+            ssh_url: format!("ssh://git@ssh.dev.azure.com{}/v3/{}", port, path),
+        });
+    }
+
+    let ssh_host = repo.host.split(':').next().unwrap();
+    Ok(CloneUrls {
+        https: format!("https://{}/{}/{}.git", repo.host, repo.user, repo.repo),
+        ssh: format!("git@{}:{}/{}.git", ssh_host, repo.user, repo.repo),
+        ssh_url: format!(
+            "ssh://git@{}{}/{}/{}.git",
+            ssh_host, port, repo.user, repo.repo
+        ),
+    })
+}
+
+pub fn format_clone_urls(cfg: &Config) -> Result<String> {
+    let urls = clone_urls(cfg)?;
+    Ok(if cfg.json {
+        serde_json::to_string(&urls).expect("Clone URLs are always serializable")
+    } else {
+        format!(
+            "HTTPS: {}\nSSH: {}\nSSH URL: {}",
+            urls.https, urls.ssh, urls.ssh_url
+        )
+    })
+}
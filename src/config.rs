@@ -1,8 +1,43 @@
+use crate::dependency::Dependency;
 use crate::error::Result;
 use crate::git::Git;
+use crate::page::{ArchiveFormat, Section, WebIde};
+use crate::upstream::Fork;
 use serde_derive::Deserialize;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PullRequestOptions {
+    pub base: Option<String>,
+    pub draft: bool,
+    pub labels: Vec<String>,
+    pub reviewers: Vec<String>,
+    pub assignees: Vec<String>,
+    pub template: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IssueOptions {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub template: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchOptions {
+    pub path: Option<String>,
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogOptions {
+    pub author: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -14,7 +49,34 @@ pub struct Config {
     pub pull_request: bool,
     pub website: bool,
     pub blame: bool,
+    pub pr_options: PullRequestOptions,
+    pub info: bool,
+    pub json: bool,
+    pub issues: bool,
+    pub new_issue: bool,
+    pub issue_options: IssueOptions,
+    pub search: Option<String>,
+    pub search_options: SearchOptions,
+    pub section: Option<Section>,
+    pub ci: bool,
+    pub released_in: bool,
+    pub log: bool,
+    pub raw: bool,
+    pub edit: bool,
+    pub web_ide: Option<WebIde>,
+    pub sourcegraph: bool,
+    pub archive: Option<ArchiveFormat>,
+    pub sha256: bool,
+    pub clone_url: bool,
+    pub package: bool,
+    pub docs: bool,
+    pub dependency: Option<Dependency>,
+    pub fork: Option<Fork>,
+    pub compare_upstream: bool,
+    pub log_options: LogOptions,
     pub remote: Option<String>,
+    // True when the repository is the one the current branch of the local checkout is tracking
+    pub local_checkout: bool,
     pub env: EnvConfig,
 }
 
@@ -38,8 +100,16 @@ pub struct EnvConfig {
     pub gitlab_ssh_port: Option<u16>,
     pub github_token: Option<String>,
     pub ghe_token: Option<String>,
+    pub gitlab_token: Option<String>,
+    pub bitbucket_token: Option<String>,
+    pub sourcegraph_url: Option<String>,
+    #[serde(default)]
+    pub sourcegraph_hosts: Vec<String>,
     pub https_proxy: Option<String>,
     pub browse_command: Option<String>,
+    pub branch_issue_pattern: Option<String>,
+    pub commit_issue_pattern: Option<String>,
+    pub cargo_home: Option<String>,
 }
 
 impl EnvConfig {
@@ -52,6 +122,14 @@ impl EnvConfig {
         if self.github_token.is_none() {
             self.github_token = env::var("GITHUB_TOKEN").ok();
         }
+        if self.cargo_home.is_none() {
+            self.cargo_home = env::var("CARGO_HOME").ok().or_else(|| {
+                env::var("HOME")
+                    .or_else(|_| env::var("USERPROFILE"))
+                    .ok()
+                    .map(|home| Path::new(&home).join(".cargo").to_string_lossy().into())
+            });
+        }
         self
     }
 }
@@ -0,0 +1,229 @@
+use crate::config::EnvConfig;
+use crate::error::{Error, ErrorKind, Result};
+use crate::git::Git;
+use crate::package::toml_value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+// Dependency of the current project resolved from lockfile and locally cached manifest
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+    pub repo_url: String,
+    // Commit hash recorded in the published package, or tag name guessed from the version
+    pub revision: String,
+    // Path of the package in the repository such as a member of monorepo
+    pub directory: Option<String>,
+}
+
+fn not_found(name: &str, msg: impl ToString) -> Box<Error> {
+    Error::new(ErrorKind::DependencyNotFound {
+        name: name.to_string(),
+        msg: msg.to_string(),
+    })
+}
+
+// Repository URL in manifest may point to a sub directory like 'https://github.com/u/r/tree/main/x'
+// or be in npm shorthand like 'github:u/r' and 'u/r'
+fn normalize_repository(repo: &str) -> Option<String> {
+    let repo = repo.trim();
+    let repo = repo.strip_prefix("git+").unwrap_or(repo);
+
+    for (prefix, host) in &[
+        ("github:", "github.com"),
+        ("gitlab:", "gitlab.com"),
+        ("bitbucket:", "bitbucket.org"),
+    ] {
+        if let Some(slug) = repo.strip_prefix(prefix) {
+            return Some(format!(
+                "https://{}/{}.git",
+                host,
+                slug.trim_end_matches(".git")
+            ));
+        }
+    }
+    if !repo.contains(':') && repo.matches('/').count() == 1 {
+        return Some(format!(
+            "https://github.com/{}.git",
+            repo.trim_end_matches(".git")
+        ));
+    }
+    if repo.starts_with("git@") || repo.starts_with("ssh://") {
+        return Some(repo.to_string());
+    }
+
+    let url = Url::parse(repo).ok()?;
+    let host = url.host_str()?;
+    let mut segments = url.path_segments()?.filter(|s| !s.is_empty());
+    let user = segments.next()?;
+    let name = segments.next()?.trim_end_matches(".git");
+    Some(format!("https://{}/{}/{}.git", host, user, name))
+}
+
+// Project root is the nearest directory containing a lockfile. Lockfiles are searched up to the
+// repository root so that a lockfile of an outer project is not used
+fn find_lockfile(cwd: &Path, repo_root: Option<&Path>) -> Option<PathBuf> {
+    for dir in cwd.ancestors() {
+        let found = ["Cargo.lock", "package-lock.json"]
+            .iter()
+            .map(|f| dir.join(f))
+            .find(|p| p.is_file());
+        if found.is_some() {
+            return found;
+        }
+        if Some(dir) == repo_root {
+            break;
+        }
+    }
+    None
+}
+
+// Only packages from registry are considered. The last entry is the latest version since entries are
+// sorted by name and version
+fn cargo_lock_version(content: &str, name: &str) -> Option<String> {
+    fn value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+        let rest = line.strip_prefix(key)?.trim_start().strip_prefix('=')?;
+        Some(rest.trim().trim_matches('"'))
+    }
+
+    let mut found = None;
+    for block in content.split("[[package]]").skip(1) {
+        let mut pkg = None;
+        let mut version = None;
+        let mut from_registry = false;
+        for line in block.lines().map(str::trim) {
+            if line.starts_with('[') {
+                break;
+            }
+            if let Some(v) = value(line, "name") {
+                pkg = Some(v);
+            } else if let Some(v) = value(line, "version") {
+                version = Some(v);
+            } else if let Some(v) = value(line, "source") {
+                from_registry = v.starts_with("registry+") || v.starts_with("sparse+");
+            }
+        }
+        if pkg == Some(name) && from_registry {
+            found = version.map(str::to_string);
+        }
+    }
+    found
+}
+
+// 'packages' is used since lockfile v2 and 'dependencies' is used in v1
+fn package_lock_version(content: &str, name: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    json["packages"][format!("node_modules/{}", name)]["version"]
+        .as_str()
+        .or_else(|| json["dependencies"][name]["version"].as_str())
+        .map(str::to_string)
+}
+
+fn cargo_dependency(name: &str, version: String, env: &EnvConfig) -> Result<Dependency> {
+    let cargo_home = env
+        .cargo_home
+        .as_ref()
+        .ok_or_else(|| not_found(name, "$CARGO_HOME is unknown"))?;
+    let dir_name = format!("{}-{}", name, version);
+    let registry_src = Path::new(cargo_home).join("registry").join("src");
+    let src = fs::read_dir(&registry_src)
+        .map_err(|e| not_found(name, e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path().join(&dir_name))
+        .find(|p| p.is_dir())
+        .ok_or_else(|| {
+            not_found(
+                name,
+                format!(
+                    "{} is not in {:?}. Please run `cargo fetch`",
+                    dir_name, registry_src
+                ),
+            )
+        })?;
+
+    let manifest = fs::read_to_string(src.join("Cargo.toml"))?;
+    let repo_url = toml_value(&manifest, &["[package]"], "repository")
+        .and_then(|v| normalize_repository(&v))
+        .ok_or_else(|| not_found(name, "No 'repository' field in Cargo.toml"))?;
+
+    // Note: `cargo package` records the commit and the path of the crate in the repository
+    let vcs_info = fs::read_to_string(src.join(".cargo_vcs_info.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok());
+    let (revision, directory) = match vcs_info {
+        Some(info) => (
+            info["git"]["sha1"].as_str().map(str::to_string),
+            info["path_in_vcs"]
+                .as_str()
+                .filter(|p| !p.is_empty())
+                .map(str::to_string),
+        ),
+        None => (None, None),
+    };
+
+    Ok(Dependency {
+        name: name.to_string(),
+        revision: revision.unwrap_or_else(|| format!("v{}", version)),
+        version,
+        repo_url,
+        directory,
+    })
+}
+
+fn npm_dependency(name: &str, version: String, project_root: &Path) -> Result<Dependency> {
+    let manifest = project_root
+        .join("node_modules")
+        .join(name)
+        .join("package.json");
+    let content = fs::read_to_string(&manifest).map_err(|e| {
+        not_found(
+            name,
+            format!(
+                "Cannot read {:?}: {}. Please run `npm install`",
+                manifest, e
+            ),
+        )
+    })?;
+    let json: serde_json::Value = serde_json::from_str(&content).map_err(|e| not_found(name, e))?;
+
+    let repository = &json["repository"];
+    let repo_url = repository
+        .as_str()
+        .or_else(|| repository["url"].as_str())
+        .and_then(normalize_repository)
+        .ok_or_else(|| not_found(name, "No 'repository' field in package.json"))?;
+
+    Ok(Dependency {
+        name: name.to_string(),
+        revision: json["gitHead"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("v{}", version)),
+        version,
+        repo_url,
+        directory: repository["directory"].as_str().map(str::to_string),
+    })
+}
+
+// Resolves the dependency without network. The version is locked in Cargo.lock or
+// package-lock.json and the repository URL is read from the manifest cached by the package manager
+pub fn resolve(name: &str, cwd: &Path, env: &EnvConfig) -> Result<Dependency> {
+    // Note: Outside a Git repository, lockfile is searched up to the root directory
+    let repo_root = Git::new(cwd, &env.git_command).root_dir().ok();
+    let lockfile = find_lockfile(cwd, repo_root.as_deref())
+        .ok_or_else(|| not_found(name, "No Cargo.lock or package-lock.json"))?;
+    let content = fs::read_to_string(&lockfile)?;
+    let project_root = lockfile.parent().unwrap();
+
+    if lockfile.ends_with("Cargo.lock") {
+        let version = cargo_lock_version(&content, name)
+            .ok_or_else(|| not_found(name, format!("Not locked in {:?}", lockfile)))?;
+        cargo_dependency(name, version, env)
+    } else {
+        let version = package_lock_version(&content, name)
+            .ok_or_else(|| not_found(name, format!("Not locked in {:?}", lockfile)))?;
+        npm_dependency(name, version, project_root)
+    }
+}
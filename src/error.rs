@@ -1,3 +1,4 @@
+use crate::page::{ArchiveFormat, Section, WebIde};
 use std::ffi::OsString;
 use std::fmt;
 use std::io;
@@ -107,6 +108,14 @@ pub enum ErrorKind {
         status: reqwest::StatusCode,
         msg: String,
     },
+    GitLabStatusFailure {
+        status: reqwest::StatusCode,
+        msg: String,
+    },
+    BitbucketStatusFailure {
+        status: reqwest::StatusCode,
+        msg: String,
+    },
     HttpClientError(reqwest::Error),
     IoError(io::Error),
     GitCommandError {
@@ -141,7 +150,47 @@ pub enum ErrorKind {
         attempts: Vec<(&'static str, Error)>,
     },
     InvalidIssueNumberFormat,
+    InvalidReferenceFormat {
+        input: String,
+    },
+    InvalidQualifiedReference {
+        input: String,
+    },
+    TrackerConfigError {
+        name: String,
+        msg: String,
+    },
+    NoTrackerMatched {
+        input: String,
+    },
+    SearchOptionNotSupported {
+        option: &'static str,
+        service: String,
+    },
+    LogOptionNotSupported {
+        option: &'static str,
+        service: String,
+    },
+    PullReqOptionNotSupported {
+        option: &'static str,
+        service: String,
+    },
+    ReferenceNotSupported {
+        kind: &'static str,
+        service: String,
+    },
+    NoTagContaining {
+        revision: String,
+    },
+    UnknownSection {
+        name: String,
+    },
+    SectionNotSupported {
+        section: Section,
+        service: String,
+    },
     LineSpecifiedForDir(PathBuf),
+    DirInPullRequest(PathBuf),
     EnvLoadError(envy::Error),
     NoLocalRepoFound {
         operation: String,
@@ -158,6 +207,47 @@ pub enum ErrorKind {
     CannotBlameDirectory {
         dir: String,
     },
+    CannotOpenRawDirectory {
+        dir: String,
+    },
+    CannotEditDirectory {
+        dir: String,
+    },
+    BranchRequired {
+        operation: &'static str,
+    },
+    UnknownWebIde {
+        name: String,
+    },
+    WebIdeNotSupported {
+        ide: WebIde,
+        service: String,
+    },
+    EditNotSupported {
+        service: String,
+    },
+    SourcegraphPageNotSupported,
+    UnknownArchiveFormat {
+        name: String,
+    },
+    ArchiveFormatNotSupported {
+        format: ArchiveFormat,
+        service: String,
+    },
+    DependencyNotFound {
+        name: String,
+        msg: String,
+    },
+    UpstreamNotFound {
+        repo: String,
+    },
+    NoUpstreamDefaultBranch,
+    CompareUpstreamNotSupported {
+        service: String,
+    },
+    NoPackageManifest {
+        dir: PathBuf,
+    },
     UserBrowseCommandFailed {
         cmd: String,
         url: String,
@@ -166,6 +256,22 @@ pub enum ErrorKind {
     SpecifiedDirNotExist {
         dir: String,
     },
+    InvalidIssuePattern {
+        pattern: String,
+        msg: String,
+    },
+    NoIssueReference {
+        branch: String,
+        revision: String,
+    },
+    MissingRequiredOption {
+        option: &'static str,
+        required: &'static str,
+    },
+    ConflictingOptions {
+        option: &'static str,
+        conflicting: &'static str,
+    },
 }
 
 impl fmt::Display for ErrorKind {
@@ -184,6 +290,8 @@ impl fmt::Display for ErrorKind {
             BrokenUrl {url, msg} => write!(f, "Broken URL '{}': {}", url, msg),
             PullReqNotSupported {service} => write!(f, "--pr or -p does not support the service {}", service),
             GitHubStatusFailure {status, msg} => write!(f, "GitHub API failure with response status {}: {}", status, msg),
+            GitLabStatusFailure {status, msg} => write!(f, "GitLab API failure with response status {}: {}", status, msg),
+            BitbucketStatusFailure {status, msg} => write!(f, "Bitbucket API failure with response status {}: {}", status, msg),
             HttpClientError(_) => write!(f, "Network request failure"),
             IoError(_) => write!(f, "I/O error happened. Git command or current directory or file path may not exist"),
             GitCommandError{stderr, args} => {
@@ -212,8 +320,20 @@ impl fmt::Display for ErrorKind {
                 }
                 Ok(())
             }
-            InvalidIssueNumberFormat => write!(f, "Issue number must start with '#' followed by numbers like #123"),
+            InvalidIssueNumberFormat => write!(f, "Issue number must start with '#' or 'GH-' followed by numbers like #123"),
+            InvalidReferenceFormat{input} => write!(f, "Reference must be one of !123 (pull request), &123 (epic), %name (milestone) or $123 (snippet) but got '{}'", input),
+            InvalidQualifiedReference{input} => write!(f, "Reference qualified with repository must be owner/repo#123 or owner/repo@{{sha}} but got '{}'", input),
+            TrackerConfigError{name, msg} => write!(f, "Invalid configuration of issue tracker '{}' in brws.tracker.{}.* of Git config: {}", name, name, msg),
+            NoTrackerMatched{input} => write!(f, "'{}' does not match to any issue tracker pattern configured in brws.tracker.*.pattern of Git config", input),
+            SearchOptionNotSupported{option, service} => write!(f, "{} option is not supported by code search of the service {}", option, service),
+            LogOptionNotSupported{option, service} => write!(f, "{} option is not supported by commit history of the service {}", option, service),
+            PullReqOptionNotSupported{option, service} => write!(f, "{} option is not supported by pull request creation page of the service {}", option, service),
+            ReferenceNotSupported{kind, service} => write!(f, "Reference to {} is not supported by the service {}", kind, service),
+            NoTagContaining{revision} => write!(f, "No tag contains the commit '{}'. It is not released yet", revision),
+            UnknownSection{name} => write!(f, "Unknown section '{}'. Available sections are: {}", name, Section::NAMES.join(", ")),
+            SectionNotSupported{section, service} => write!(f, "Section '{}' is not supported by the service {}", section, service),
             LineSpecifiedForDir(path) => write!(f, "Directory cannot have line number: {:?}", path),
+            DirInPullRequest(path) => write!(f, "Directory cannot be opened in diff of pull request: {:?}. Please specify file path", path),
             EnvLoadError(_) => write!(f, "Cannot load environment variable"),
             NoLocalRepoFound{operation} => write!(f, ".git directory was not found. For {}, local repository must be known", operation),
             NoSearchResult{query} => write!(f, "No repository was hit for query '{}'", query),
@@ -221,8 +341,26 @@ impl fmt::Display for ErrorKind {
             GheTokenRequired => write!(f, "GitHub Enterprise requires API token. Please set $GIT_BRWS_GHE_TOKEN"),
             BlameWithoutFilePath => write!(f, "File path is not given to blame"),
             CannotBlameDirectory{dir} => write!(f, "Cannot blame directory '{}'. Please specify file path", dir),
+            CannotOpenRawDirectory{dir} => write!(f, "Cannot open raw content of directory '{}'. Please specify file path", dir),
+            CannotEditDirectory{dir} => write!(f, "Cannot edit directory '{}'. Please specify file path", dir),
+            BranchRequired{operation} => write!(f, "Branch is required for {} but HEAD is detached. Please specify branch with --branch", operation),
+            UnknownWebIde{name} => write!(f, "Unknown web IDE '{}'. Available IDEs are: github.dev, vscode.dev, gitpod, codespaces", name),
+            WebIdeNotSupported{ide, service} => write!(f, "Web IDE {} is not available for the service {}", ide, service),
+            SourcegraphPageNotSupported => write!(f, "Only repository, file, directory, commit, diff and code search pages can be opened on Sourcegraph. Blame page is not available"),
+            EditNotSupported{service} => write!(f, "Editing a file via URL is not supported by the service {}", service),
+            UnknownArchiveFormat{name} => write!(f, "Unknown archive format '{}'. Available formats are: tar.gz, zip", name),
+            ArchiveFormatNotSupported{format, service} => write!(f, "Archive format {} is not available for the service {}", format, service),
+            DependencyNotFound{name, msg} => write!(f, "Cannot resolve dependency '{}' from local lockfile and package cache: {}", name, msg),
+            UpstreamNotFound{repo} => write!(f, "Upstream of {} was not found. It is resolved via GitHub API or 'upstream' remote", repo),
+            NoUpstreamDefaultBranch => write!(f, "Default branch of the upstream is unknown. Please run `git remote set-head upstream --auto`"),
+            CompareUpstreamNotSupported{service} => write!(f, "Comparing a fork with its upstream is not supported by the service {}", service),
+            NoPackageManifest{dir} => write!(f, "No manifest of published package (Cargo.toml, package.json, pyproject.toml, go.mod, *.gemspec) was found from {:?} to the repository root", dir),
             UserBrowseCommandFailed{cmd, url, msg} => write!(f, "Command '{}' failed to open URL {}. Please check $GIT_BRWS_BROWSE_COMMAND. stderr: {}", cmd, url, msg),
             SpecifiedDirNotExist{dir} => write!(f, "Specified directory '{}' with -d option does not exist", dir),
+            InvalidIssuePattern{pattern, msg} => write!(f, "Invalid regular expression '{}' to extract issue references: {}", pattern, msg),
+            NoIssueReference{branch, revision} => write!(f, "No issue reference was found in branch name '{}' nor commit message of '{}'", branch, revision),
+            MissingRequiredOption{option, required} => write!(f, "{} option requires {} option", option, required),
+            ConflictingOptions{option, conflicting} => write!(f, "{} option cannot be used with {} option", option, conflicting),
         }
     }
 }
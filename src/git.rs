@@ -21,13 +21,19 @@ pub struct Git<'a> {
 
 impl<'a> Git<'a> {
     pub fn command<S: AsRef<OsStr> + Debug>(&self, args: &[S]) -> Result<String> {
+        let stdout = self.command_bytes(args)?;
+        Ok(String::from_utf8_lossy(&stdout).trim().to_string())
+    }
+
+    // Raw stdout is necessary for binary output such as `git archive`
+    fn command_bytes<S: AsRef<OsStr> + Debug>(&self, args: &[S]) -> Result<Vec<u8>> {
         let out = Command::new(&self.command)
             .arg("-C")
             .arg(self.cwd)
             .args(args)
             .output()?;
         if out.status.success() {
-            Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+            Ok(out.stdout)
         } else {
             Error::err(ErrorKind::GitCommandError {
                 stderr: String::from_utf8_lossy(&out.stderr)
@@ -53,6 +59,54 @@ impl<'a> Git<'a> {
         Ok(stdout.splitn(2, ' ').next().unwrap().to_string())
     }
 
+    // Returns the earliest tag containing the commit. Tags are sorted by date of the tag (or of the
+    // tagged commit for lightweight tags)
+    pub fn earliest_tag_containing(&self, commit: impl AsRef<str>) -> Result<Option<String>> {
+        let commit = commit.as_ref();
+        let stdout = self
+            .command(&["tag", "--contains", commit, "--sort=creatordate"])
+            .map_err(|e| object_not_found("commit", e, commit))?;
+        Ok(stdout.lines().next().map(str::to_string))
+    }
+
+    pub fn file_content(&self, commit: impl AsRef<str>, path: impl AsRef<str>) -> Result<String> {
+        let object = format!("{}:{}", commit.as_ref(), path.as_ref());
+        self.command(&["cat-file", "-p", &object])
+            .map_err(|e| object_not_found("file", e, &object))
+    }
+
+    // Git LFS pointer file is a small text file starting with the version line
+    //   https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md
+    pub fn is_lfs_pointer(&self, commit: impl AsRef<str>, path: impl AsRef<str>) -> Result<bool> {
+        let object = format!("{}:{}", commit.as_ref(), path.as_ref());
+        let size: usize = self
+            .command(&["cat-file", "-s", &object])
+            .map_err(|e| object_not_found("file", e, &object))?
+            .parse()
+            .unwrap_or(usize::MAX);
+        if size > 1024 {
+            return Ok(false);
+        }
+        let content = self.command(&["cat-file", "-p", &object])?;
+        Ok(content.starts_with("version https://git-lfs.github.com/spec/"))
+    }
+
+    pub fn archive(
+        &self,
+        revision: impl AsRef<str>,
+        format: impl AsRef<str>,
+        prefix: impl AsRef<str>,
+    ) -> Result<Vec<u8>> {
+        let revision = revision.as_ref();
+        self.command_bytes(&[
+            "archive",
+            &format!("--format={}", format.as_ref()),
+            &format!("--prefix={}", prefix.as_ref()),
+            revision,
+        ])
+        .map_err(|e| object_not_found("revision", e, revision))
+    }
+
     pub fn remote_url(&self, name: impl AsRef<str>) -> Result<String> {
         // XXX:
         // `git remote get-url {name}` is not available because it's added recently (at 2.6.1).
@@ -141,6 +195,88 @@ impl<'a> Git<'a> {
         let rev = format!("{}@{}", branch, remote);
         self.command(&["rev-parse", "--abbrev-ref", "--symbolic", rev.as_str()])
     }
+
+    // Returns {remote}/{branch} which {remote}/HEAD points to
+    pub fn remote_default_branch(&self, remote_name: impl AsRef<str>) -> Result<String> {
+        let rev = format!("{}/HEAD", remote_name.as_ref());
+        self.command(&["rev-parse", "--abbrev-ref", rev.as_str()])
+    }
+
+    // Returns pairs of local branch name and its upstream branch name ({remote}/{branch}) for
+    // local branches tracking some remote branch
+    pub fn tracking_branches(&self) -> Result<Vec<(String, String)>> {
+        let stdout = self.command(&[
+            "for-each-ref",
+            "--format=%(refname:short) %(upstream:short)",
+            "refs/heads/",
+        ])?;
+        Ok(stdout
+            .lines()
+            .filter_map(|l| l.split_once(' '))
+            .filter(|(_, upstream)| !upstream.is_empty())
+            .map(|(local, upstream)| (local.to_string(), upstream.to_string()))
+            .collect())
+    }
+
+    pub fn merge_base(&self, lhs: impl AsRef<str>, rhs: impl AsRef<str>) -> Result<String> {
+        self.command(&["merge-base", lhs.as_ref(), rhs.as_ref()])
+    }
+
+    // Returns number of commits in {base}..{head}
+    pub fn count_commits(&self, base: impl AsRef<str>, head: impl AsRef<str>) -> Result<usize> {
+        let range = format!("{}..{}", base.as_ref(), head.as_ref());
+        let stdout = self.command(&["rev-list", "--count", range.as_str()])?;
+        Ok(stdout.parse().unwrap_or(0))
+    }
+
+    // Returns pairs of key and value of config entries whose keys match the regular expression.
+    // Empty when no entry matches
+    pub fn config_entries(&self, key_regex: impl AsRef<str>) -> Result<Vec<(String, String)>> {
+        let stdout = match self.command(&["config", "--get-regexp", key_regex.as_ref()]) {
+            Ok(stdout) => stdout,
+            // Note: `git config --get-regexp` exits with non-zero status when nothing matches
+            Err(err) => match err.kind() {
+                ErrorKind::GitCommandError { stderr, .. } if stderr.is_empty() => {
+                    return Ok(vec![]);
+                }
+                _ => return Err(err),
+            },
+        };
+        Ok(stdout
+            .lines()
+            .map(|l| match l.split_once(' ') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => (l.to_string(), String::new()),
+            })
+            .collect())
+    }
+
+    pub fn commit_message(&self, rev: impl AsRef<str>) -> Result<String> {
+        let rev = rev.as_ref();
+        self.command(&["log", "-1", "--format=%B", rev])
+            .map_err(|e| object_not_found("commit", e, rev))
+    }
+
+    // Returns pairs of subject and body of commits in {base}..{head} from older to newer
+    pub fn commit_messages(
+        &self,
+        base: impl AsRef<str>,
+        head: impl AsRef<str>,
+    ) -> Result<Vec<(String, String)>> {
+        let range = format!("{}..{}", base.as_ref(), head.as_ref());
+        // Note: Separate subject and body with US (0x1f) and each commit with RS (0x1e)
+        let stdout =
+            self.command(&["log", "--reverse", "--format=%s%x1f%b%x1e", range.as_str()])?;
+        Ok(stdout
+            .split('\x1e')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(|c| match c.split_once('\x1f') {
+                Some((subject, body)) => (subject.to_string(), body.trim().to_string()),
+                None => (c.to_string(), String::new()),
+            })
+            .collect())
+    }
 }
 
 impl<'a> Git<'a> {
@@ -1,3 +1,4 @@
+use crate::config::EnvConfig;
 use crate::error::{Error, ErrorKind, Result};
 use reqwest::{header, Proxy, StatusCode};
 use reqwest::{Client as ReqwestClient, RequestBuilder, Response};
@@ -12,12 +13,79 @@ pub struct ParentRepoOwner {
 pub struct ParentRepo {
     pub name: String,
     pub owner: ParentRepoOwner,
+    pub default_branch: Option<String>,
 }
 #[derive(Debug, Deserialize)]
 pub struct Repo {
     pub parent: Option<ParentRepo>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+#[derive(Debug, Deserialize)]
+pub struct PullRequestRef {
+    #[serde(rename = "ref")]
+    pub name: String,
+    pub sha: String,
+    // '{owner}:{branch}'
+    #[serde(default)]
+    pub label: String,
+}
+#[derive(Debug, Deserialize)]
+pub struct PullRequest {
+    pub number: usize,
+    pub html_url: String,
+    pub title: String,
+    pub state: String,
+    #[serde(default)]
+    pub merged: bool,
+    #[serde(default)]
+    pub draft: bool,
+    pub user: User,
+    pub base: PullRequestRef,
+    pub head: PullRequestRef,
+    #[serde(default)]
+    pub requested_reviewers: Vec<User>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Review {
+    pub user: User,
+    pub state: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Status {
+    pub state: String,
+    pub target_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CombinedStatus {
+    pub state: String,
+    pub total_count: usize,
+    #[serde(default)]
+    pub statuses: Vec<Status>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CheckRun {
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+}
+#[derive(Debug, Deserialize)]
+struct Release {
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRuns {
+    check_runs: Vec<CheckRun>,
+}
+
 #[derive(Debug, Deserialize)]
 struct Issue {
     html_url: String,
@@ -70,6 +138,19 @@ impl<'a> Client<'a> {
         })
     }
 
+    // Token is chosen by the endpoint. Token is always required for GitHub Enterprise
+    pub fn from_env(endpoint: &'a str, env: &'a EnvConfig) -> Result<Self> {
+        let token = if endpoint == "api.github.com" {
+            &env.github_token
+        } else {
+            if env.ghe_token.is_none() {
+                return Error::err(ErrorKind::GheTokenRequired);
+            }
+            &env.ghe_token
+        };
+        Self::build(endpoint, token, &env.https_proxy)
+    }
+
     pub async fn send(&self, mut req: RequestBuilder) -> Result<Response> {
         req = req.header(header::ACCEPT, "application/vnd.github.v3+json");
         if let Some(token) = &self.token {
@@ -118,6 +199,25 @@ impl<'a> Client<'a> {
         }
     }
 
+    // Returns URLs of issues and pull requests which match the text. At most `limit` URLs are
+    // returned in order of best match
+    pub async fn search_issue_urls(
+        &self,
+        text: &str,
+        owner: &str,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        let query = format!("{} repo:{}/{}", text, owner, repo);
+        let per_page = limit.to_string();
+        let params = [("q", query.as_str()), ("per_page", per_page.as_str())];
+        let url = format!("https://{}/search/issues", self.endpoint);
+        let req = self.client.get(url.as_str()).query(&params);
+        let res = self.send(req).await?;
+        let issues: Issues = res.json().await?;
+        Ok(issues.items.into_iter().map(|i| i.html_url).collect())
+    }
+
     pub async fn repo(&self, author: impl AsRef<str>, repo: impl AsRef<str>) -> Result<Repo> {
         let author = author.as_ref();
         let repo = repo.as_ref();
@@ -128,6 +228,134 @@ impl<'a> Client<'a> {
         Ok(repo)
     }
 
+    pub async fn pull_request(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        number: usize,
+    ) -> Result<PullRequest> {
+        let url = format!(
+            "https://{}/repos/{}/{}/pulls/{}",
+            self.endpoint,
+            owner.as_ref(),
+            repo.as_ref(),
+            number,
+        );
+        let req = self.client.get(url.as_str());
+        let res = self.send(req).await?;
+        let pr: PullRequest = res.json().await?;
+        Ok(pr)
+    }
+
+    // Only the first page is fetched. It is enough to find heads of stacked pull requests
+    pub async fn open_pull_requests(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+    ) -> Result<Vec<PullRequest>> {
+        let url = format!(
+            "https://{}/repos/{}/{}/pulls",
+            self.endpoint,
+            owner.as_ref(),
+            repo.as_ref(),
+        );
+        let params = [("state", "open"), ("per_page", "100")];
+        let req = self.client.get(url.as_str()).query(&params);
+        let res = self.send(req).await?;
+        let prs: Vec<PullRequest> = res.json().await?;
+        Ok(prs)
+    }
+
+    pub async fn pull_request_reviews(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        number: usize,
+    ) -> Result<Vec<Review>> {
+        let url = format!(
+            "https://{}/repos/{}/{}/pulls/{}/reviews",
+            self.endpoint,
+            owner.as_ref(),
+            repo.as_ref(),
+            number,
+        );
+        let params = [("per_page", "100")];
+        let req = self.client.get(url.as_str()).query(&params);
+        let res = self.send(req).await?;
+        let reviews: Vec<Review> = res.json().await?;
+        Ok(reviews)
+    }
+
+    pub async fn combined_status(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        sha: impl AsRef<str>,
+    ) -> Result<CombinedStatus> {
+        let url = format!(
+            "https://{}/repos/{}/{}/commits/{}/status",
+            self.endpoint,
+            owner.as_ref(),
+            repo.as_ref(),
+            sha.as_ref(),
+        );
+        let req = self.client.get(url.as_str());
+        let res = self.send(req).await?;
+        let status: CombinedStatus = res.json().await?;
+        Ok(status)
+    }
+
+    pub async fn check_runs(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        sha: impl AsRef<str>,
+    ) -> Result<Vec<CheckRun>> {
+        let url = format!(
+            "https://{}/repos/{}/{}/commits/{}/check-runs",
+            self.endpoint,
+            owner.as_ref(),
+            repo.as_ref(),
+            sha.as_ref(),
+        );
+        let params = [("per_page", "100")];
+        let req = self.client.get(url.as_str()).query(&params);
+        let res = self.send(req).await?;
+        let runs: CheckRuns = res.json().await?;
+        Ok(runs.check_runs)
+    }
+
+    // Returns None when no release is published for the tag
+    pub async fn release_url(
+        &self,
+        owner: impl AsRef<str>,
+        repo: impl AsRef<str>,
+        tag: impl AsRef<str>,
+    ) -> Result<Option<String>> {
+        let url = format!(
+            "https://{}/repos/{}/{}/releases/tags/{}",
+            self.endpoint,
+            owner.as_ref(),
+            repo.as_ref(),
+            tag.as_ref(),
+        );
+        let req = self.client.get(url.as_str());
+        match self.send(req).await {
+            Ok(res) => {
+                let release: Release = res.json().await?;
+                Ok(Some(release.html_url))
+            }
+            Err(err) => match err.kind() {
+                ErrorKind::GitHubStatusFailure { status, .. }
+                    if *status == StatusCode::NOT_FOUND =>
+                {
+                    Ok(None)
+                }
+                _ => Err(err),
+            },
+        }
+    }
+
     pub async fn most_popular_repo_by_name(&self, name: impl AsRef<str>) -> Result<SearchedRepo> {
         // XXX: No query syntax for exact matching to repository name. Use `in:name` instead though
         // it's matching to substrings.
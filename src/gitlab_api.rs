@@ -0,0 +1,139 @@
+use crate::config::EnvConfig;
+use crate::error::{Error, ErrorKind, Result};
+use reqwest::{Client as ReqwestClient, Proxy, RequestBuilder, StatusCode};
+use serde_derive::Deserialize;
+use url::form_urlencoded;
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Pages {
+    url: String,
+}
+
+// URL of the site in response of Pages API. Empty URL means no site is deployed
+pub fn pages_url_from_json(json: &str) -> Option<String> {
+    let pages: Pages = serde_json::from_str(json).ok()?;
+    Some(pages.url).filter(|u| !u.is_empty())
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    web_url: String,
+}
+
+// URL of the first merge request in response of merge requests API. Empty list means no merge
+// request is open for the branch
+pub fn merge_request_url_from_json(json: &str) -> Option<String> {
+    let mrs: Vec<MergeRequest> = serde_json::from_str(json).ok()?;
+    mrs.into_iter().next().map(|mr| mr.web_url)
+}
+
+pub struct Client<'a> {
+    client: ReqwestClient,
+    token: Option<&'a str>,
+    host: &'a str,
+}
+
+impl<'a> Client<'a> {
+    pub fn build(host: &'a str, env: &'a EnvConfig) -> Result<Self> {
+        let mut b = ReqwestClient::builder().user_agent("git-brws");
+
+        if let Some(ref p) = env.https_proxy {
+            if !p.is_empty() {
+                b = b.proxy(Proxy::https(p)?);
+            }
+        }
+
+        Ok(Self {
+            client: b.build()?,
+            token: env.gitlab_token.as_deref(),
+            host,
+        })
+    }
+
+    // Project can be specified with URL-encoded path instead of its numeric ID
+    fn get(&self, user: &str, repo: &str, path: &str) -> RequestBuilder {
+        let project: String =
+            form_urlencoded::byte_serialize(format!("{}/{}", user, repo).as_bytes()).collect();
+        let url = format!("https://{}/api/v4/projects/{}{}", self.host, project, path);
+        let req = self.client.get(url.as_str());
+        match self.token {
+            Some(token) => req.header("PRIVATE-TOKEN", token),
+            None => req,
+        }
+    }
+
+    // Numeric ID of the project. Comparison with a fork requires it instead of its path
+    pub async fn project_id(&self, user: &str, repo: &str) -> Result<u64> {
+        let res = self.get(user, repo, "").send().await?;
+        let status = res.status();
+        if status != StatusCode::OK {
+            return Error::err(ErrorKind::GitLabStatusFailure {
+                status,
+                msg: res.text().await.unwrap(),
+            });
+        }
+
+        let project: Project = res.json().await?;
+        Ok(project.id)
+    }
+
+    pub async fn release_exists(&self, user: &str, repo: &str, tag: &str) -> Result<bool> {
+        let tag: String = form_urlencoded::byte_serialize(tag.as_bytes()).collect();
+        let res = self
+            .get(user, repo, &format!("/releases/{}", tag))
+            .send()
+            .await?;
+        match res.status() {
+            StatusCode::OK => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => Error::err(ErrorKind::GitLabStatusFailure {
+                status,
+                msg: res.text().await.unwrap(),
+            }),
+        }
+    }
+
+    // Pages settings require Maintainer role of the project so API token is usually necessary.
+    // None is returned when no site is deployed
+    pub async fn pages_url(&self, user: &str, repo: &str) -> Result<Option<String>> {
+        let res = self.get(user, repo, "/pages").send().await?;
+        match res.status() {
+            StatusCode::OK => Ok(pages_url_from_json(&res.text().await?)),
+            StatusCode::NOT_FOUND => Ok(None),
+            status => Error::err(ErrorKind::GitLabStatusFailure {
+                status,
+                msg: res.text().await.unwrap(),
+            }),
+        }
+    }
+
+    // Open merge request whose source branch is the branch. None is returned when no merge request
+    // is open for the branch
+    pub async fn open_merge_request_url(
+        &self,
+        user: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Option<String>> {
+        let query: String = form_urlencoded::Serializer::new(String::new())
+            .append_pair("source_branch", branch)
+            .append_pair("state", "opened")
+            .finish();
+        let res = self
+            .get(user, repo, &format!("/merge_requests?{}", query))
+            .send()
+            .await?;
+        match res.status() {
+            StatusCode::OK => Ok(merge_request_url_from_json(&res.text().await?)),
+            status => Error::err(ErrorKind::GitLabStatusFailure {
+                status,
+                msg: res.text().await.unwrap(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::async_runtime;
+use crate::config::Config;
+use crate::error::{Error, ErrorKind, Result};
+use crate::pull_request::{self, Summary};
+use crate::service::{resolve_repository, Service};
+
+fn format_summary(summary: &Summary) -> String {
+    fn or_none(s: Option<&str>) -> &str {
+        s.unwrap_or("-")
+    }
+    format!(
+        "#{} {}\nURL: {}\nState: {}\nDraft: {}\nAuthor: {}\nBase: {}\nHead: {}\nReview: {}\nChecks: {}",
+        summary.number,
+        summary.title,
+        summary.url,
+        summary.state,
+        if summary.draft { "yes" } else { "no" },
+        summary.author,
+        summary.base,
+        summary.head,
+        or_none(summary.review_decision),
+        or_none(summary.checks),
+    )
+}
+
+// Text of the summary and the warning to be reported separately
+#[derive(Debug)]
+pub struct PullRequestInfo {
+    pub text: String,
+    pub warning: Option<String>,
+}
+
+// Returns None when no pull request is found for the branch
+pub fn pull_request(cfg: &Config) -> Result<Option<PullRequestInfo>> {
+    let repo = resolve_repository(cfg)?;
+    let endpoint = match repo.service {
+        Service::GitHub { api_endpoint } => api_endpoint,
+        _ => {
+            return Error::err(ErrorKind::PullReqNotSupported { service: repo.host });
+        }
+    };
+
+    let summary = async_runtime::blocking(pull_request::find_summary(
+        &endpoint, &repo.user, &repo.repo, cfg,
+    ))?;
+
+    Ok(summary.map(|s| PullRequestInfo {
+        text: if cfg.json {
+            serde_json::to_string(&s).expect("Summary is always serializable")
+        } else {
+            format_summary(&s)
+        },
+        warning: s.stale_base.map(|b| b.to_string()),
+    }))
+}
@@ -0,0 +1,100 @@
+use crate::config::Config;
+use crate::error::{Error, ErrorKind, ExpectedNumberOfArgs, Result};
+use crate::page::Page;
+use crate::tracker::{load_trackers, Tracker};
+use regex::Regex;
+
+// Issue number separated by '/', '-' or '_' in branch name such as 'fix/1234-login-crash'
+const DEFAULT_BRANCH_PATTERN: &str = r"(?:^|[/_-])(\d+)(?:[/_-]|$)";
+// Issue number such as 'Fixes #1234' or issue key of external tracker such as 'Refs: ABC-99' in
+// commit message
+const DEFAULT_COMMIT_PATTERN: &str = r"#(\d+)\b|\b([A-Z][A-Z0-9]*-\d+)\b";
+
+fn compile(pattern: &Option<String>, default: &str) -> Result<Regex> {
+    let pattern = pattern.as_deref().unwrap_or(default);
+    Regex::new(pattern).map_err(|e| {
+        Error::new(ErrorKind::InvalidIssuePattern {
+            pattern: pattern.to_string(),
+            msg: format!("{}", e),
+        })
+    })
+}
+
+// Number is an issue of the hosting service. Other references are keys of external trackers.
+// Keys which no tracker matches are ignored since they may not be issues (e.g. 'UTF-8')
+fn issue_page(reference: &str, trackers: &[Tracker]) -> Option<Page> {
+    if let Ok(number) = reference.parse() {
+        return Some(Page::Issue { number });
+    }
+    trackers
+        .iter()
+        .find_map(|t| t.issue_url(reference))
+        .map(|url| Page::TrackerIssue {
+            key: reference.to_string(),
+            url,
+        })
+}
+
+// Issue reference is the first capture group which participates in the match. When the pattern
+// has no capture group, the whole match is used. Next search starts at the end of the reference
+// so that a separator after it is shared with the next reference as in 'fix-12-34'.
+fn extract(pattern: &Regex, text: &str, trackers: &[Tracker], pages: &mut Vec<Page>) {
+    let mut start = 0;
+    while let Some(caps) = pattern.captures_at(text, start) {
+        let matched = caps
+            .iter()
+            .skip(1)
+            .flatten()
+            .next()
+            .or_else(|| caps.get(0))
+            .unwrap();
+        if let Some(page) = issue_page(matched.as_str(), trackers) {
+            if !pages.contains(&page) {
+                pages.push(page);
+            }
+        }
+        // Note: Advance at least one character on empty match to avoid infinite loop
+        start = match text[matched.end()..].chars().next() {
+            _ if matched.end() > start => matched.end(),
+            Some(c) => matched.end() + c.len_utf8(),
+            None => break,
+        };
+    }
+}
+
+// Returns pages of issues referenced by the branch name and the commit message in the order of
+// appearance. References in the branch name come first.
+pub fn parse_pages(cfg: &Config) -> Result<Vec<Page>> {
+    if cfg.args.len() > 1 {
+        return Error::err(ErrorKind::WrongNumberOfArgs {
+            expected: ExpectedNumberOfArgs::Range(0, 1),
+            actual: cfg.args.len(),
+            kind: "issue references".to_string(),
+        });
+    }
+
+    let branch_pattern = compile(&cfg.env.branch_issue_pattern, DEFAULT_BRANCH_PATTERN)?;
+    let commit_pattern = compile(&cfg.env.commit_issue_pattern, DEFAULT_COMMIT_PATTERN)?;
+
+    let git = cfg.git();
+    let trackers = load_trackers(&git)?;
+    let branch = match &cfg.branch {
+        Some(b) => b.clone(),
+        None => git.current_branch()?,
+    };
+    let revision = cfg.args.first().map(String::as_str).unwrap_or("HEAD");
+    let message = git.commit_message(revision)?;
+
+    let mut pages = vec![];
+    extract(&branch_pattern, &branch, &trackers, &mut pages);
+    extract(&commit_pattern, &message, &trackers, &mut pages);
+
+    if pages.is_empty() {
+        return Error::err(ErrorKind::NoIssueReference {
+            branch,
+            revision: revision.to_string(),
+        });
+    }
+
+    Ok(pages)
+}
@@ -1,13 +1,24 @@
 mod async_runtime;
+mod bitbucket_api;
 mod config;
+mod dependency;
 mod git;
 mod github_api;
+mod gitlab_api;
+mod issue_ref;
+mod package;
 mod page;
 mod pull_request;
 mod service;
+mod tracker;
+mod upstream;
+mod website;
 
+pub mod archive;
 pub mod argv;
+pub mod clone;
 pub mod error;
+pub mod info;
 pub mod url;
 
 #[cfg(test)]
@@ -1,22 +1,54 @@
 use git_brws::argv::Parsed;
-use git_brws::{error, url};
+use git_brws::{archive, clone, error, info, url};
 use std::env::args;
 use std::process::exit;
 
-fn run() -> error::Result<()> {
+// Exit status when no pull request is found with --info
+const EXIT_NO_PULL_REQUEST: i32 = 1;
+
+fn run() -> error::Result<i32> {
     match Parsed::parse_iter(args())? {
         Parsed::Help(usage) => eprintln!("{}", usage),
         Parsed::Version(version) => println!("{}", version),
-        Parsed::OpenPage(ref opts) if opts.stdout => println!("{}", url::build_url(opts)?),
-        Parsed::OpenPage(ref opts) => url::browse(&url::build_url(opts)?, &opts.env)?,
+        Parsed::OpenPage(ref opts) if opts.info => match info::pull_request(opts)? {
+            Some(info) => {
+                if let Some(warning) = info.warning {
+                    eprintln!("Warning: {}", warning);
+                }
+                println!("{}", info.text);
+            }
+            None => return Ok(EXIT_NO_PULL_REQUEST),
+        },
+        Parsed::OpenPage(ref opts) if opts.clone_url => {
+            println!("{}", clone::format_clone_urls(opts)?)
+        }
+        Parsed::OpenPage(ref opts) if opts.sha256 => println!("{}", archive::checksum(opts)?),
+        Parsed::OpenPage(ref opts) => {
+            let mut warnings = vec![];
+            let urls = url::build_urls(opts, &mut warnings)?;
+            for warning in warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            for url in urls {
+                if opts.stdout {
+                    println!("{}", url);
+                } else {
+                    url::browse(&url, &opts.env)?;
+                }
+            }
+        }
     }
-    Ok(())
+    Ok(0)
 }
 
 // Note: fn main() -> error::Result<()> is not available since it uses {:?} for error message.
 fn main() {
-    if let Err(e) = run() {
-        e.eprintln();
-        exit(3);
+    match run() {
+        Ok(0) => {}
+        Ok(status) => exit(status),
+        Err(e) => {
+            e.eprintln();
+            exit(3);
+        }
     }
 }
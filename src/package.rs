@@ -0,0 +1,148 @@
+use crate::error::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+// Package registry where the package in local manifest is published
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Registry {
+    CratesIo,
+    Npm,
+    PyPI,
+    GoModules,
+    RubyGems,
+}
+
+impl Registry {
+    pub fn package_url(self, name: &str) -> String {
+        match self {
+            Registry::CratesIo => format!("https://crates.io/crates/{}", name),
+            Registry::Npm => format!("https://www.npmjs.com/package/{}", name),
+            Registry::PyPI => format!("https://pypi.org/project/{}/", name),
+            Registry::GoModules => format!("https://pkg.go.dev/{}", name),
+            Registry::RubyGems => format!("https://rubygems.org/gems/{}", name),
+        }
+    }
+
+    // Note: pkg.go.dev is the documentation site of Go modules as well
+    pub fn docs_url(self, name: &str) -> String {
+        match self {
+            Registry::CratesIo => format!("https://docs.rs/{}", name),
+            Registry::Npm => format!("https://www.jsdocs.io/package/{}", name),
+            Registry::PyPI => format!(
+                "https://{}.readthedocs.io/",
+                name.to_ascii_lowercase().replace(['_', '.'], "-"),
+            ),
+            Registry::GoModules => format!("https://pkg.go.dev/{}", name),
+            Registry::RubyGems => format!("https://www.rubydoc.info/gems/{}", name),
+        }
+    }
+}
+
+// Finds the value of the key in the first TOML table matching one of the headers. Empty header
+// means the top-level table. Only a subset of TOML which is enough for manifests is parsed
+pub fn toml_value(content: &str, headers: &[&str], key: &str) -> Option<String> {
+    lazy_static! {
+        static ref VALUE: Regex = Regex::new(r#"^\s*=\s*["']([^"']+)["']"#).unwrap();
+    }
+    let mut in_table = headers.contains(&"");
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_table = headers.contains(&line);
+            continue;
+        }
+        if !in_table {
+            continue;
+        }
+        if let Some(c) = line.strip_prefix(key).and_then(|rest| VALUE.captures(rest)) {
+            return Some(c[1].to_string());
+        }
+    }
+    None
+}
+
+fn cargo_package(content: &str) -> Option<String> {
+    toml_value(content, &["[package]"], "name")
+}
+
+fn pyproject_package(content: &str) -> Option<String> {
+    toml_value(content, &["[project]", "[tool.poetry]"], "name")
+}
+
+// Private packages such as the root of npm workspace are never published
+fn npm_package(content: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    if json["private"].as_bool() == Some(true) {
+        return None;
+    }
+    json["name"].as_str().map(str::to_string)
+}
+
+fn go_module(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let path = line.trim().strip_prefix("module")?.trim();
+        Some(path.trim_matches('"').to_string()).filter(|p| !p.is_empty())
+    })
+}
+
+// Gem name is usually assigned as 'spec.name = "foo"'. Fall back into the file name
+fn gemspec_package(content: &str, path: &Path) -> Option<String> {
+    lazy_static! {
+        static ref NAME: Regex = Regex::new(r#"\.name\s*=\s*["']([^"']+)["']"#).unwrap();
+    }
+    match NAME.captures(content) {
+        Some(c) => Some(c[1].to_string()),
+        None => path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(str::to_string),
+    }
+}
+
+// Extracts package name from content of manifest file
+type ParseManifest = fn(&str) -> Option<String>;
+
+// Returns None when no manifest of a published package is in the directory. A workspace manifest
+// without package is skipped so that the member containing the path is picked
+fn package_in_dir(dir: &Path) -> Result<Option<(Registry, String)>> {
+    let manifests: &[(&str, Registry, ParseManifest)] = &[
+        ("Cargo.toml", Registry::CratesIo, cargo_package),
+        ("package.json", Registry::Npm, npm_package),
+        ("pyproject.toml", Registry::PyPI, pyproject_package),
+        ("go.mod", Registry::GoModules, go_module),
+    ];
+    for (file, registry, parse) in manifests {
+        let path = dir.join(file);
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(name) = parse(&fs::read_to_string(path)?) {
+            return Ok(Some((*registry, name)));
+        }
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map(|e| e == "gemspec").unwrap_or(false) {
+            if let Some(name) = gemspec_package(&fs::read_to_string(&path)?, &path) {
+                return Ok(Some((Registry::RubyGems, name)));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+// Searches manifests from the directory up to the repository root
+pub fn find_package(dir: &Path, repo_root: &Path) -> Result<Option<(Registry, String)>> {
+    for dir in dir.ancestors() {
+        if let Some(found) = package_in_dir(dir)? {
+            return Ok(Some(found));
+        }
+        if dir == repo_root {
+            break;
+        }
+    }
+    Ok(None)
+}
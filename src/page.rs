@@ -1,8 +1,13 @@
 use crate::config::Config;
+use crate::dependency::Dependency;
 use crate::error::{Error, ErrorKind, ExpectedNumberOfArgs, Result};
 use crate::git::Git;
+use crate::package::{find_package, Registry};
+use crate::tracker::load_trackers;
 use std::fmt;
 use std::fs;
+use std::path::Path;
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum DiffOp {
@@ -25,6 +30,163 @@ pub enum Line {
     Range(usize, usize), // start and end
 }
 
+// Sub page of repository. The name is portable across services and mapped to the path of each
+// service
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Section {
+    Releases,
+    Tags,
+    Branches,
+    Ci,
+    Wiki,
+    PullRequests,
+    Issues,
+    Milestones,
+    Labels,
+    Security,
+    Insights,
+    Forks,
+    Settings,
+}
+
+impl Section {
+    pub const NAMES: &'static [&'static str] = &[
+        "releases",
+        "tags",
+        "branches",
+        "ci",
+        "wiki",
+        "pulls",
+        "issues",
+        "milestones",
+        "labels",
+        "security",
+        "insights",
+        "forks",
+        "settings",
+    ];
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Section::Releases => "releases",
+            Section::Tags => "tags",
+            Section::Branches => "branches",
+            Section::Ci => "ci",
+            Section::Wiki => "wiki",
+            Section::PullRequests => "pulls",
+            Section::Issues => "issues",
+            Section::Milestones => "milestones",
+            Section::Labels => "labels",
+            Section::Security => "security",
+            Section::Insights => "insights",
+            Section::Forks => "forks",
+            Section::Settings => "settings",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Section {
+    type Err = Box<Error>;
+
+    // Note: Some aliases are accepted since each service calls the same section differently
+    fn from_str(s: &str) -> Result<Section> {
+        match s.to_ascii_lowercase().as_str() {
+            "releases" => Ok(Section::Releases),
+            "tags" => Ok(Section::Tags),
+            "branches" => Ok(Section::Branches),
+            "ci" | "actions" | "pipelines" | "builds" => Ok(Section::Ci),
+            "wiki" => Ok(Section::Wiki),
+            "pulls" | "prs" | "pull-requests" | "merge-requests" => Ok(Section::PullRequests),
+            "issues" => Ok(Section::Issues),
+            "milestones" => Ok(Section::Milestones),
+            "labels" => Ok(Section::Labels),
+            "security" => Ok(Section::Security),
+            "insights" | "graphs" => Ok(Section::Insights),
+            "forks" => Ok(Section::Forks),
+            "settings" => Ok(Section::Settings),
+            _ => Error::err(ErrorKind::UnknownSection {
+                name: s.to_string(),
+            }),
+        }
+    }
+}
+
+// Web-based editor to open a file with
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum WebIde {
+    GitHubDev,
+    VsCodeDev,
+    Gitpod,
+    Codespaces,
+}
+
+impl fmt::Display for WebIde {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WebIde::GitHubDev => "github.dev",
+            WebIde::VsCodeDev => "vscode.dev",
+            WebIde::Gitpod => "gitpod",
+            WebIde::Codespaces => "codespaces",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for WebIde {
+    type Err = Box<Error>;
+
+    fn from_str(s: &str) -> Result<WebIde> {
+        match s.to_ascii_lowercase().as_str() {
+            "github.dev" => Ok(WebIde::GitHubDev),
+            "vscode.dev" => Ok(WebIde::VsCodeDev),
+            "gitpod" => Ok(WebIde::Gitpod),
+            "codespaces" => Ok(WebIde::Codespaces),
+            _ => Error::err(ErrorKind::UnknownWebIde {
+                name: s.to_string(),
+            }),
+        }
+    }
+}
+
+// Format of source archive to download
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ArchiveFormat {
+    TarGz,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArchiveFormat::TarGz => "tar.gz",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+}
+
+impl fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl FromStr for ArchiveFormat {
+    type Err = Box<Error>;
+
+    fn from_str(s: &str) -> Result<ArchiveFormat> {
+        match s.to_ascii_lowercase().as_str() {
+            "tar.gz" | "tgz" => Ok(ArchiveFormat::TarGz),
+            "zip" => Ok(ArchiveFormat::Zip),
+            _ => Error::err(ErrorKind::UnknownArchiveFormat {
+                name: s.to_string(),
+            }),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Page {
     Open {
@@ -48,10 +210,116 @@ pub enum Page {
     Issue {
         number: usize,
     },
+    Section {
+        section: Section,
+    },
+    // Edit page of the file on the branch. When IDE is set, the file is opened in the web IDE
+    EditFile {
+        relative_path: String,
+        branch: String,
+        line: Option<Line>,
+        ide: Option<WebIde>,
+    },
+    // Raw content of the file. Media URL is used for a file tracked by Git LFS
+    RawFile {
+        relative_path: String,
+        hash: String,
+        lfs: bool,
+    },
+    // Source archive of the tag or the commit. Name is tag name when tag is true, otherwise commit
+    // hash
+    Archive {
+        name: String,
+        tag: bool,
+        format: ArchiveFormat,
+    },
+    // Comparison of the branch in the fork against the base branch of its upstream. Base is the
+    // default branch of the upstream
+    CompareUpstream {
+        base: Option<String>,
+        user: String,
+        repo: String,
+        branch: String,
+    },
+    // Page of the package on the registry. Documentation page is opened when docs is true
+    Package {
+        registry: Registry,
+        name: String,
+        docs: bool,
+    },
+    // Commit history of the revision. When path is set, only commits touching the path are listed.
+    // Hash is a branch name or a full commit hash
+    History {
+        relative_path: Option<String>,
+        hash: String,
+    },
+    // CI runs of the commit. Branch is set when the commit is the head of the branch
+    Ci {
+        hash: String,
+        branch: Option<String>,
+    },
+    IssueSearch {
+        text: String,
+    },
+    CodeSearch {
+        query: String,
+    },
+    // Page to create a new issue. File is FileOrDir page to refer in the body
+    NewIssue {
+        file: Option<Box<Page>>,
+        commit: Option<String>,
+    },
     Tag {
         tagname: String,
         commit: String,
     },
+    PullRequestFile {
+        relative_path: String,
+        line: Option<Line>,
+    },
+    // References in GitLab syntax: !123, &123, %name, $123
+    PullRequest {
+        number: usize,
+    },
+    Epic {
+        number: usize,
+    },
+    Milestone {
+        name: String,
+    },
+    Snippet {
+        number: usize,
+    },
+    // Issue on external issue tracker such as Jira. URL is resolved while parsing
+    TrackerIssue {
+        key: String,
+        url: String,
+    },
+    // Page in other repository referred with owner/repo#12 or owner/repo@sha
+    Qualified {
+        user: String,
+        repo: String,
+        page: Box<Page>,
+    },
+}
+
+fn is_slug_component(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+// Splits reference qualified with repository like 'owner/repo#12' or 'owner/repo@sha' into
+// owner, repo and the rest starting with '#' or '@'
+pub fn split_qualified_reference(arg: &str) -> Option<(&str, &str, &str)> {
+    let idx = arg.find(['#', '@'])?;
+    let (slug, rest) = arg.split_at(idx);
+    let (user, repo) = slug.split_once('/')?;
+    if is_slug_component(user) && is_slug_component(repo) {
+        Some((user, repo, rest))
+    } else {
+        None
+    }
 }
 
 struct BrowsePageParser<'a> {
@@ -162,6 +430,29 @@ impl<'a> BrowsePageParser<'a> {
         }
     }
 
+    fn relative_path(&self, path: &Path) -> Result<String> {
+        let repo_root = self.git.root_dir()?;
+        Ok(path
+            .strip_prefix(&repo_root)
+            .map_err(|_| {
+                Error::new(ErrorKind::FileDirNotInRepo {
+                    repo_root: repo_root.to_owned(),
+                    path: path.to_owned(),
+                })
+            })?
+            .to_str()
+            .expect("Failed to convert path into UTF-8 string")
+            .to_string())
+    }
+
+    // Branch specified with --branch or current branch. 'HEAD' is returned in detached HEAD state
+    fn branch_name(&self) -> Result<String> {
+        match &self.cfg.branch {
+            Some(b) => Ok(b.clone()),
+            None => self.git.current_branch(),
+        }
+    }
+
     fn try_parse_file_or_dir(&self) -> Result<Page> {
         let len = self.cfg.args.len();
         if len != 1 && len != 2 {
@@ -178,23 +469,22 @@ impl<'a> BrowsePageParser<'a> {
                     dir: path.to_string_lossy().into(),
                 });
             }
+            if self.cfg.raw {
+                return Error::err(ErrorKind::CannotOpenRawDirectory {
+                    dir: path.to_string_lossy().into(),
+                });
+            }
+            if self.cfg.edit || self.cfg.web_ide.is_some() {
+                return Error::err(ErrorKind::CannotEditDirectory {
+                    dir: path.to_string_lossy().into(),
+                });
+            }
             if line.is_some() {
                 return Error::err(ErrorKind::LineSpecifiedForDir(path));
             }
         }
 
-        let repo_root = self.git.root_dir()?;
-        let relative_path = path
-            .strip_prefix(&repo_root)
-            .map_err(|_| {
-                Error::new(ErrorKind::FileDirNotInRepo {
-                    repo_root: repo_root.to_owned(),
-                    path: path.clone(),
-                })
-            })?
-            .to_str()
-            .expect("Failed to convert path into UTF-8 string")
-            .to_string();
+        let relative_path = self.relative_path(&path)?;
 
         let mut hash = if len == 2 {
             self.git.hash(self.cfg.args[1].as_str())?
@@ -205,15 +495,29 @@ impl<'a> BrowsePageParser<'a> {
         // Fall back into branch name when the commit is not existing in remote branch (#12)
         //
         // Ignore this check when the local branch does not point to any remote branch
-        let remote_contains_hash = match self.git.remote_branch(&self.cfg.remote, &self.cfg.branch)
-        {
-            Ok(remote_branch) => self.git.remote_contains(&hash, &remote_branch)?,
-            Err(_) => true, // Ignore check
+        let upstream_branch = self
+            .cfg
+            .fork
+            .as_ref()
+            .and_then(|f| f.upstream_default_branch.as_ref());
+        let remote_contains_hash = if self.cfg.fork.is_some() {
+            // Note: Remote branch of the fork is unrelated. Check the default branch of upstream
+            match (&self.cfg.remote, upstream_branch) {
+                (Some(remote), Some(branch)) => self
+                    .git
+                    .remote_contains(&hash, format!("{}/{}", remote, branch))?,
+                _ => true, // Ignore check
+            }
+        } else {
+            match self.git.remote_branch(&self.cfg.remote, &self.cfg.branch) {
+                Ok(remote_branch) => self.git.remote_contains(&hash, &remote_branch)?,
+                Err(_) => true, // Ignore check
+            }
         };
         if !remote_contains_hash {
-            hash = match &self.cfg.branch {
-                Some(b) => b.clone(),
-                None => self.git.current_branch()?,
+            hash = match upstream_branch {
+                Some(branch) => branch.clone(),
+                None => self.branch_name()?,
             };
         };
 
@@ -225,25 +529,451 @@ impl<'a> BrowsePageParser<'a> {
         })
     }
 
+    // Without argument, the head of the branch is used. Branch is unknown in detached HEAD state
+    fn parse_ci(&self) -> Result<Page> {
+        match self.cfg.args.as_slice() {
+            [] => {
+                let branch = self.branch_name()?;
+                if branch == "HEAD" {
+                    return Ok(Page::Ci {
+                        hash: self.git.hash("HEAD")?,
+                        branch: None,
+                    });
+                }
+                Ok(Page::Ci {
+                    hash: self.git.hash(&branch)?,
+                    branch: Some(branch),
+                })
+            }
+            [rev] => Ok(Page::Ci {
+                hash: self.git.hash(rev)?,
+                branch: None,
+            }),
+            _ => self.wrong_number_of_args(ExpectedNumberOfArgs::Range(0, 1), "CI page"),
+        }
+    }
+
+    // Edit page requires branch instead of commit hash. Revision given as the second argument is
+    // used as branch name
+    fn parse_edit_file(&self) -> Result<Page> {
+        let (relative_path, line) = match self.try_parse_file_or_dir()? {
+            Page::FileOrDir {
+                relative_path,
+                line,
+                ..
+            } => (relative_path, line),
+            p => unreachable!("Unexpected page for file or directory: {:?}", p),
+        };
+        let branch = match self.cfg.args.get(1) {
+            Some(b) => b.clone(),
+            None => self.branch_name()?,
+        };
+        if branch == "HEAD" {
+            return Error::err(ErrorKind::BranchRequired {
+                operation: "editing a file",
+            });
+        }
+        Ok(Page::EditFile {
+            relative_path,
+            branch,
+            line,
+            ide: self.cfg.web_ide,
+        })
+    }
+
+    // Note: Line number is ignored since raw content has no anchor
+    fn parse_raw_file(&self) -> Result<Page> {
+        match self.try_parse_file_or_dir()? {
+            Page::FileOrDir {
+                relative_path,
+                hash,
+                ..
+            } => Ok(Page::RawFile {
+                lfs: self.git.is_lfs_pointer(&hash, &relative_path)?,
+                relative_path,
+                hash,
+            }),
+            p => unreachable!("Unexpected page for file or directory: {:?}", p),
+        }
+    }
+
+    // Path and revision are resolved in the same way as file or directory page. When the argument
+    // is not a path, it is treated as revision
+    fn parse_history(&self) -> Result<Page> {
+        if self.cfg.args.is_empty() {
+            let mut hash = self.branch_name()?;
+            if hash == "HEAD" {
+                hash = self.git.hash("HEAD")?;
+            }
+            return Ok(Page::History {
+                relative_path: None,
+                hash,
+            });
+        }
+
+        let err = match self.try_parse_file_or_dir() {
+            Ok(Page::FileOrDir {
+                relative_path,
+                hash,
+                ..
+            }) => {
+                return Ok(Page::History {
+                    relative_path: Some(relative_path),
+                    hash,
+                })
+            }
+            Ok(p) => unreachable!("Unexpected page for file or directory: {:?}", p),
+            Err(err) => err,
+        };
+
+        // Note: Branch name is kept to open the history of the branch. Other revisions such as
+        // 'HEAD~3' or '@{u}' are resolved into commit hashes since the services don't know them
+        match self.cfg.args.as_slice() {
+            [rev] => match self.git.hash(rev) {
+                Ok(hash) => Ok(Page::History {
+                    relative_path: None,
+                    hash: if self.git.hash(format!("refs/heads/{}", rev)).is_ok() {
+                        rev.clone()
+                    } else {
+                        hash
+                    },
+                }),
+                Err(_) => Err(err),
+            },
+            _ => Err(err),
+        }
+    }
+
+    fn parse_released_in(&self) -> Result<Page> {
+        if self.cfg.args.len() > 1 {
+            return self.wrong_number_of_args(ExpectedNumberOfArgs::Range(0, 1), "release lookup");
+        }
+        let revision = self.cfg.args.first().map(String::as_str).unwrap_or("HEAD");
+        let tagname = match self.git.earliest_tag_containing(revision)? {
+            Some(tag) => tag,
+            None => {
+                return Error::err(ErrorKind::NoTagContaining {
+                    revision: revision.to_string(),
+                })
+            }
+        };
+        Ok(Page::Tag {
+            commit: self.git.tag_hash(&tagname)?,
+            tagname,
+        })
+    }
+
+    // Tag name is preferred to commit hash since archives of tags have stable URLs
+    fn parse_archive(&self, format: ArchiveFormat) -> Result<Page> {
+        if self.cfg.args.len() > 1 {
+            return self.wrong_number_of_args(ExpectedNumberOfArgs::Range(0, 1), "archive");
+        }
+        if let Some(arg) = self.cfg.args.first() {
+            if self.git.tag_hash(arg).is_ok() {
+                return Ok(Page::Archive {
+                    name: arg.clone(),
+                    tag: true,
+                    format,
+                });
+            }
+        }
+        let revision = self.cfg.args.first().map(String::as_str).unwrap_or("HEAD");
+        Ok(Page::Archive {
+            name: self.git.hash(revision)?,
+            tag: false,
+            format,
+        })
+    }
+
+    // Paths are relative to the root of the dependency package since its repository is not in local.
+    // Without argument, the directory of the package in the repository is opened
+    fn parse_dependency_path(&self, dep: &Dependency) -> Result<Page> {
+        let (path, line) = match self.cfg.args.len() {
+            0 => ("", None),
+            1 => self.parse_path_and_line(),
+            _ => {
+                return self
+                    .wrong_number_of_args(ExpectedNumberOfArgs::Range(0, 1), "dependency path")
+            }
+        };
+        let path = path.trim_start_matches("./").trim_matches('/');
+        let relative_path = match (&dep.directory, path) {
+            (Some(dir), "") => dir.clone(),
+            (Some(dir), path) => format!("{}/{}", dir.trim_end_matches('/'), path),
+            (None, path) => path.to_string(),
+        };
+        Ok(Page::FileOrDir {
+            relative_path,
+            hash: self.branch_name()?,
+            line,
+            blame: self.cfg.blame,
+        })
+    }
+
+    // Branch of the fork is given as argument or the current branch is used
+    fn parse_compare_upstream(&self) -> Result<Page> {
+        let fork = self
+            .cfg
+            .fork
+            .as_ref()
+            .expect("Fork is always resolved with --compare-upstream");
+        let branch = match self.cfg.args.as_slice() {
+            [] => self.branch_name()?,
+            [branch] => branch.clone(),
+            _ => {
+                return self
+                    .wrong_number_of_args(ExpectedNumberOfArgs::Range(0, 1), "upstream comparison")
+            }
+        };
+        if branch == "HEAD" {
+            return Error::err(ErrorKind::BranchRequired {
+                operation: "comparing with upstream",
+            });
+        }
+        Ok(Page::CompareUpstream {
+            base: fork.upstream_default_branch.clone(),
+            user: fork.user.clone(),
+            repo: fork.repo.clone(),
+            branch,
+        })
+    }
+
+    // Manifest is searched from the directory of the given path (or current directory) up to the
+    // repository root
+    fn parse_package(&self) -> Result<Page> {
+        let dir = match self.cfg.args.as_slice() {
+            [] => self.cfg.cwd.clone(),
+            [path] => {
+                let path = fs::canonicalize(path)?;
+                self.relative_path(&path)?; // Ensure the path is in the repository
+                if path.is_dir() {
+                    path
+                } else {
+                    path.parent().unwrap().to_path_buf()
+                }
+            }
+            _ => return self.wrong_number_of_args(ExpectedNumberOfArgs::Range(0, 1), "package"),
+        };
+        let repo_root = self.git.root_dir()?;
+        match find_package(&dir, &repo_root)? {
+            Some((registry, name)) => Ok(Page::Package {
+                registry,
+                name,
+                docs: self.cfg.docs,
+            }),
+            None => Error::err(ErrorKind::NoPackageManifest { dir }),
+        }
+    }
+
+    // File in diff of pull request. Unlike FileOrDir, commit hash is not necessary since the
+    // pull request determines the revision
+    fn try_parse_pull_request_file(&self) -> Result<Page> {
+        if self.cfg.args.len() != 1 {
+            return self
+                .wrong_number_of_args(ExpectedNumberOfArgs::Single(1), "file in pull request");
+        }
+
+        let (path, line) = self.parse_path_and_line();
+        let path = fs::canonicalize(path)?;
+        if path.is_dir() {
+            return Error::err(ErrorKind::DirInPullRequest(path));
+        }
+
+        Ok(Page::PullRequestFile {
+            relative_path: self.relative_path(&path)?,
+            line,
+        })
+    }
+
+    fn try_parse_tracker_issue(&self) -> Result<Page> {
+        if self.cfg.args.len() != 1 {
+            return self.wrong_number_of_args(ExpectedNumberOfArgs::Single(1), "issue tracker key");
+        }
+
+        let key = &self.cfg.args[0];
+        for tracker in load_trackers(&self.git)? {
+            if let Some(url) = tracker.issue_url(key) {
+                return Ok(Page::TrackerIssue {
+                    key: key.clone(),
+                    url,
+                });
+            }
+        }
+
+        Error::err(ErrorKind::NoTrackerMatched { input: key.clone() })
+    }
+
     fn try_parse_issue_number(&self) -> Result<Page> {
         if self.cfg.args.len() != 1 {
             return self.wrong_number_of_args(ExpectedNumberOfArgs::Single(1), "issue number");
         }
 
         let arg = &self.cfg.args[0];
-        if !arg.starts_with('#') {
-            return Error::err(ErrorKind::InvalidIssueNumberFormat);
-        }
-        let arg = &arg[1..];
+        // Note: 'GH-12' is an alias of '#12' on GitHub
+        let arg = match arg.strip_prefix('#').or_else(|| arg.strip_prefix("GH-")) {
+            Some(number) => number,
+            None => return Error::err(ErrorKind::InvalidIssueNumberFormat),
+        };
         let number: usize = arg
             .parse()
             .map_err(|_| Error::new(ErrorKind::InvalidIssueNumberFormat))?;
         Ok(Page::Issue { number })
     }
+
+    // Text which is not a number after '#' like '#login crash'
+    fn try_parse_issue_search(&self) -> Result<Page> {
+        if self.cfg.args.len() != 1 {
+            return self.wrong_number_of_args(ExpectedNumberOfArgs::Single(1), "issue search");
+        }
+
+        match self.cfg.args[0].strip_prefix('#').map(str::trim) {
+            Some(text) if !text.is_empty() => Ok(Page::IssueSearch {
+                text: text.to_string(),
+            }),
+            _ => Error::err(ErrorKind::InvalidIssueNumberFormat),
+        }
+    }
+
+    // Unlike other pages, commit is not resolved locally since it may not exist in local repository
+    fn try_parse_qualified_reference(&self) -> Result<Page> {
+        if self.cfg.args.len() != 1 {
+            return self
+                .wrong_number_of_args(ExpectedNumberOfArgs::Single(1), "qualified reference");
+        }
+
+        let arg = &self.cfg.args[0];
+        let invalid = || Error::new(ErrorKind::InvalidQualifiedReference { input: arg.clone() });
+        let (user, repo, rest) = split_qualified_reference(arg).ok_or_else(invalid)?;
+
+        let page = if let Some(number) = rest.strip_prefix('#') {
+            let number = number.parse().map_err(|_| invalid())?;
+            Page::Issue { number }
+        } else {
+            let hash = &rest[1..];
+            if hash.len() < 4 || hash.len() > 40 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            Page::Commit {
+                hash: hash.to_string(),
+            }
+        };
+
+        Ok(Page::Qualified {
+            user: user.to_string(),
+            repo: repo.to_string(),
+            page: Box::new(page),
+        })
+    }
+
+    fn try_parse_reference(&self) -> Result<Page> {
+        if self.cfg.args.len() != 1 {
+            return self.wrong_number_of_args(ExpectedNumberOfArgs::Single(1), "reference");
+        }
+
+        let arg = &self.cfg.args[0];
+        let invalid = || Error::new(ErrorKind::InvalidReferenceFormat { input: arg.clone() });
+        let mut chars = arg.chars();
+        let prefix = chars.next().ok_or_else(invalid)?;
+        let rest = chars.as_str();
+
+        if prefix == '%' {
+            // Milestone name can be quoted like %"v2.0" when it contains spaces
+            let name = if rest.len() >= 2 && rest.starts_with('"') && rest.ends_with('"') {
+                &rest[1..rest.len() - 1]
+            } else {
+                rest
+            };
+            if name.is_empty() {
+                return Err(invalid());
+            }
+            return Ok(Page::Milestone {
+                name: name.to_string(),
+            });
+        }
+
+        let number = rest.parse().map_err(|_| invalid())?;
+        match prefix {
+            '!' => Ok(Page::PullRequest { number }),
+            '&' => Ok(Page::Epic { number }),
+            '$' => Ok(Page::Snippet { number }),
+            _ => Err(invalid()),
+        }
+    }
 }
 
 pub fn parse_page(cfg: &Config) -> Result<Page> {
-    let mut attempts = Vec::with_capacity(5);
+    let mut attempts = Vec::with_capacity(9);
+
+    let parser = BrowsePageParser {
+        cfg,
+        git: cfg.git(),
+    };
+
+    if let Some(section) = cfg.section {
+        if !cfg.args.is_empty() {
+            return parser.wrong_number_of_args(ExpectedNumberOfArgs::Single(0), "section");
+        }
+        return Ok(Page::Section { section });
+    }
+
+    if cfg.compare_upstream {
+        return parser.parse_compare_upstream();
+    }
+
+    if let Some(ref dep) = cfg.dependency {
+        if !cfg.args.is_empty() || dep.directory.is_some() {
+            return parser.parse_dependency_path(dep);
+        }
+    }
+
+    if cfg.ci {
+        return parser.parse_ci();
+    }
+
+    if cfg.released_in {
+        return parser.parse_released_in();
+    }
+
+    if cfg.package || cfg.docs {
+        return parser.parse_package();
+    }
+
+    if let Some(format) = cfg.archive {
+        return parser.parse_archive(format);
+    }
+
+    if cfg.log {
+        return parser.parse_history();
+    }
+
+    if cfg.raw {
+        return parser.parse_raw_file();
+    }
+
+    if cfg.edit || cfg.web_ide.is_some() {
+        return parser.parse_edit_file();
+    }
+
+    // Note: Ignore any arguments for code search as well as website
+    if let Some(ref query) = cfg.search {
+        return Ok(Page::CodeSearch {
+            query: query.clone(),
+        });
+    }
+
+    if cfg.new_issue {
+        if cfg.args.is_empty() {
+            return Ok(Page::NewIssue {
+                file: None,
+                commit: None,
+            });
+        }
+        let file = parser.try_parse_file_or_dir()?;
+        return Ok(Page::NewIssue {
+            file: Some(Box::new(file)),
+            commit: parser.git.hash("HEAD").ok(),
+        });
+    }
 
     // Note: Ignore any arguments when opening a website
     if cfg.args.is_empty() || cfg.website || cfg.pull_request {
@@ -251,22 +981,45 @@ pub fn parse_page(cfg: &Config) -> Result<Page> {
             return Error::err(ErrorKind::BlameWithoutFilePath);
         }
 
+        // Note: When the argument is not a file path, ignore it and open the pull request page
+        if cfg.pull_request && !cfg.website && !cfg.args.is_empty() {
+            match parser.try_parse_pull_request_file() {
+                Ok(p) => return Ok(p),
+                Err(err) => match err.kind() {
+                    ErrorKind::DirInPullRequest(_) => return Err(err),
+                    _ => { /* Fall through */ }
+                },
+            }
+        }
+
         return Ok(Page::Open {
             website: cfg.website,
             pull_request: cfg.pull_request,
         });
     }
 
-    let parser = BrowsePageParser {
-        cfg,
-        git: cfg.git(),
-    };
+    // Note: Configured trackers take precedence since issue numbers like #123 may be meaningless
+    // when the repository uses an external issue tracker
+    match parser.try_parse_tracker_issue() {
+        Ok(p) => return Ok(p),
+        Err(e) => attempts.push(("Issue tracker", *e)),
+    }
 
     match parser.try_parse_issue_number() {
         Ok(p) => return Ok(p),
         Err(e) => attempts.push(("Issue number", *e)),
     }
 
+    match parser.try_parse_issue_search() {
+        Ok(p) => return Ok(p),
+        Err(e) => attempts.push(("Issue search", *e)),
+    }
+
+    match parser.try_parse_reference() {
+        Ok(p) => return Ok(p),
+        Err(e) => attempts.push(("Reference", *e)),
+    }
+
     // Note: Early return for --blame
     match parser.try_parse_file_or_dir() {
         Ok(p) => return Ok(p),
@@ -277,6 +1030,12 @@ pub fn parse_page(cfg: &Config) -> Result<Page> {
         },
     }
 
+    // Note: Try after file since 'dir/file#12' is also a valid file path with line number
+    match parser.try_parse_qualified_reference() {
+        Ok(p) => return Ok(p),
+        Err(e) => attempts.push(("Qualified reference", *e)),
+    }
+
     match parser.try_parse_diff() {
         Ok(p) => return Ok(p),
         Err(e) => attempts.push(("Diff", *e)),
@@ -1,17 +1,23 @@
 use crate::config::{Config, EnvConfig};
 use crate::error::{Error, ErrorKind, Result};
 use crate::github_api;
+use serde_derive::Serialize;
 use std::borrow::Cow;
+use std::fmt;
 
 #[derive(PartialEq, Debug)]
 pub enum Page<'a, 'b> {
     Existing {
         url: String,
+        // Checked only for open pull request since base of closed one is no longer updated
+        stale_base: Option<StaleBase>,
     },
     New {
         author: Cow<'a, str>,
         repo: Cow<'b, str>,
         branch: String,
+        // None means the default branch of the repository
+        base: Option<String>,
     },
     NewAtParent {
         author: Cow<'a, str>,
@@ -21,6 +27,243 @@ pub enum Page<'a, 'b> {
     },
 }
 
+// Title and body to fill in 'Create Pull Request' form
+#[derive(PartialEq, Debug, Default)]
+pub struct Description {
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+impl Description {
+    // Single commit is described with its subject and body. Multiple commits are described with
+    // a bullet list of their subjects.
+    pub fn from_commits(commits: &[(String, String)]) -> Description {
+        match commits {
+            [] => Description::default(),
+            [(subject, body)] => Description {
+                title: Some(subject.clone()),
+                body: if body.is_empty() {
+                    None
+                } else {
+                    Some(body.clone())
+                },
+            },
+            commits => {
+                let list = commits
+                    .iter()
+                    .map(|(subject, _)| format!("- {}", subject))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                Description {
+                    title: None,
+                    body: Some(list),
+                }
+            }
+        }
+    }
+
+    // Note: Description is made from local commits. When they are not available (e.g. --repo is
+    // specified or the branch does not exist locally), empty description is returned.
+    pub fn from_local_commits(branch: &str, base: Option<&str>, cfg: &Config) -> Description {
+        let git = cfg.git();
+        let remote = remote_name(cfg);
+        let base = match base {
+            Some(base) => {
+                let remote_base = format!("{}/{}", remote, base);
+                if git.hash(&remote_base).is_ok() {
+                    remote_base
+                } else {
+                    base.to_string()
+                }
+            }
+            None => match git.remote_default_branch(remote) {
+                Ok(b) => b,
+                Err(_) => return Description::default(),
+            },
+        };
+        match git.commit_messages(&base, branch) {
+            Ok(commits) => Description::from_commits(&commits),
+            Err(_) => Description::default(),
+        }
+    }
+}
+
+fn remote_name(cfg: &Config) -> &str {
+    cfg.remote.as_deref().unwrap_or("origin")
+}
+
+// Pull requests from forks are excluded since their heads are not branches of the repository
+fn is_same_repo(pr: &github_api::PullRequest) -> bool {
+    let owner = |label: &str| label.split(':').next().map(str::to_string);
+    owner(&pr.head.label) == owner(&pr.base.label)
+}
+
+// Detect the nearest ancestor of the branch in a stack of topic branches. Candidates are local
+// branches tracking remote branches, heads of open pull requests and the default branch of the
+// remote. Distance to a candidate is the number of commits from its merge base to the branch.
+// None is returned when the default branch is the nearest or local repository is not available.
+pub fn detect_stacked_base(
+    branch: &str,
+    cfg: &Config,
+    open_prs: &[github_api::PullRequest],
+) -> Option<String> {
+    let git = cfg.git();
+    let remote = remote_name(cfg);
+    let prefix = format!("{}/", remote);
+    let head = git.hash(branch).ok()?;
+    let default = git
+        .remote_default_branch(remote)
+        .ok()
+        .and_then(|b| b.strip_prefix(&prefix).map(str::to_string));
+
+    // Pairs of revision and name of the branch at remote
+    let mut candidates = git
+        .tracking_branches()
+        .ok()?
+        .into_iter()
+        .filter(|(local, upstream)| local != branch && upstream.starts_with(&prefix))
+        .map(|(local, upstream)| (local, upstream[prefix.len()..].to_string()))
+        .collect::<Vec<_>>();
+    // Note: Head of open pull request may not be checked out locally. Its commit is used when
+    // it was already fetched
+    candidates.extend(
+        open_prs
+            .iter()
+            .filter(|pr| pr.head.name != branch && is_same_repo(pr))
+            .map(|pr| (pr.head.sha.clone(), pr.head.name.clone())),
+    );
+    if let Some(ref default) = default {
+        candidates.push((format!("{}{}", prefix, default), default.clone()));
+    }
+
+    let mut nearest: Option<(usize, &str)> = None;
+    for (rev, name) in candidates.iter() {
+        let base = match git.merge_base(rev, &head) {
+            Ok(base) => base,
+            Err(_) => continue,
+        };
+        if base == head {
+            continue; // The candidate is stacked on the branch
+        }
+        let distance = match git.count_commits(&base, &head) {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+        let is_default = default.as_ref() == Some(name);
+        let is_nearer = match nearest {
+            None => true,
+            Some((d, _)) => distance < d || distance == d && is_default,
+        };
+        if is_nearer {
+            nearest = Some((distance, name));
+        }
+    }
+
+    match nearest {
+        Some((_, name)) if default.as_deref() != Some(name) => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+// Base branch of a new pull request. None means the default branch of the repository.
+pub fn base_branch(
+    branch: &str,
+    cfg: &Config,
+    open_prs: &[github_api::PullRequest],
+) -> Option<String> {
+    cfg.pr_options
+        .base
+        .clone()
+        .or_else(|| detect_stacked_base(branch, cfg, open_prs))
+}
+
+pub fn branch_name(cfg: &Config) -> Result<String> {
+    match cfg.branch {
+        Some(ref b) => Ok(b.clone()),
+        None => cfg.git().current_branch(),
+    }
+}
+
+// Parse 'https://{host}/{owner}/{repo}/pull/{number}' into owner, repo and number
+fn parse_pr_url(url: &str) -> Option<(&str, &str, usize)> {
+    let mut split = url.rsplit('/');
+    let number = split.next()?.parse().ok()?;
+    if split.next()? != "pull" {
+        return None;
+    }
+    let repo = split.next()?;
+    let owner = split.next()?;
+    Some((owner, repo, number))
+}
+
+// Base branch of the existing pull request which no longer matches the stack of local topic
+// branches (e.g. the parent branch was merged or the branch was rebased onto other branch)
+#[derive(PartialEq, Debug)]
+pub struct StaleBase {
+    pub pr_base: String,
+    // None means the default branch
+    pub local_base: Option<String>,
+}
+
+impl fmt::Display for StaleBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Base branch of the pull request is '{}', but the branch is based on ",
+            self.pr_base,
+        )?;
+        match self.local_base {
+            Some(ref base) => write!(f, "'{}' locally", base),
+            None => write!(f, "the default branch locally"),
+        }
+    }
+}
+
+// This check is best effort. None is returned when local repository is not available
+pub fn stale_base(
+    branch: &str,
+    pr_base: &str,
+    open_prs: &[github_api::PullRequest],
+    cfg: &Config,
+) -> Option<StaleBase> {
+    let git = cfg.git();
+    let remote = remote_name(cfg);
+    let prefix = format!("{}/", remote);
+    let default = git.remote_default_branch(remote).ok();
+    let mut topic_branches = git
+        .tracking_branches()
+        .ok()?
+        .into_iter()
+        .filter(|(local, upstream)| {
+            local != branch && upstream.starts_with(&prefix) && default.as_ref() != Some(upstream)
+        })
+        .map(|(_, upstream)| upstream[prefix.len()..].to_string())
+        .collect::<Vec<_>>();
+    topic_branches.extend(
+        open_prs
+            .iter()
+            .filter(|pr| pr.head.name != branch && is_same_repo(pr))
+            .map(|pr| pr.head.name.clone()),
+    );
+    if topic_branches.is_empty() {
+        return None;
+    }
+
+    let local_base = detect_stacked_base(branch, cfg, open_prs);
+    let is_stale = match local_base {
+        Some(ref base) => base != pr_base,
+        None => topic_branches.iter().any(|b| b == pr_base),
+    };
+    if is_stale {
+        Some(StaleBase {
+            pr_base: pr_base.to_string(),
+            local_base,
+        })
+    } else {
+        None
+    }
+}
+
 async fn find_github_pr_url_for_branch<'a, 'b>(
     branch: impl AsRef<str>,
     endpoint: &str,
@@ -29,15 +272,7 @@ async fn find_github_pr_url_for_branch<'a, 'b>(
     env: &EnvConfig,
 ) -> Result<Page<'a, 'b>> {
     let branch = branch.as_ref();
-    let token = if endpoint == "api.github.com" {
-        &env.github_token
-    } else {
-        if env.ghe_token.is_none() {
-            return Error::err(ErrorKind::GheTokenRequired);
-        }
-        &env.ghe_token
-    };
-    let client = github_api::Client::build(endpoint, &token, &env.https_proxy)?;
+    let client = github_api::Client::from_env(endpoint, env)?;
 
     let (pr_url, fetched_repo) = futures::join!(
         // Note: Search pull request URL in the case where the repository is an original, not a
@@ -51,7 +286,10 @@ async fn find_github_pr_url_for_branch<'a, 'b>(
     );
 
     if let Some(url) = pr_url? {
-        return Ok(Page::Existing { url });
+        return Ok(Page::Existing {
+            url,
+            stale_base: None,
+        });
     }
 
     let fetched_repo = fetched_repo?;
@@ -65,7 +303,10 @@ async fn find_github_pr_url_for_branch<'a, 'b>(
             .find_pr_url(branch, owner.as_str(), repo.as_str(), Some(author))
             .await?
         {
-            Ok(Page::Existing { url })
+            Ok(Page::Existing {
+                url,
+                stale_base: None,
+            })
         } else {
             Ok(Page::NewAtParent {
                 author: Cow::Owned(owner),
@@ -79,27 +320,218 @@ async fn find_github_pr_url_for_branch<'a, 'b>(
             author: Cow::Borrowed(author),
             repo: Cow::Borrowed(repo),
             branch: branch.to_string(),
+            base: None,
         })
     }
 }
 
+// Heads and bases of open pull requests are only needed for stacked topic branches. Fetching them
+// is best effort
+async fn open_pull_requests(
+    endpoint: &str,
+    owner: &str,
+    repo: &str,
+    env: &EnvConfig,
+) -> Vec<github_api::PullRequest> {
+    match github_api::Client::from_env(endpoint, env) {
+        Ok(client) => client
+            .open_pull_requests(owner, repo)
+            .await
+            .unwrap_or_default(),
+        Err(_) => vec![],
+    }
+}
+
 pub async fn find_page<'a, 'b>(
     endpoint: &str,
     author: &'a str,
     repo: &'b str,
     cfg: &Config,
 ) -> Result<Page<'a, 'b>> {
-    match cfg.branch {
-        Some(ref b) => find_github_pr_url_for_branch(b, endpoint, author, repo, &cfg.env).await,
-        None => {
-            find_github_pr_url_for_branch(
-                cfg.git().current_branch()?,
-                endpoint,
-                author,
-                repo,
-                &cfg.env,
-            )
-            .await
+    let branch = branch_name(cfg)?;
+    let mut page = find_github_pr_url_for_branch(&branch, endpoint, author, repo, &cfg.env).await?;
+    match &mut page {
+        Page::Existing {
+            url,
+            stale_base: stale,
+        } => {
+            if let Some((owner, repo, _)) = parse_pr_url(url) {
+                let open_prs = open_pull_requests(endpoint, owner, repo, &cfg.env).await;
+                *stale = open_prs
+                    .iter()
+                    .find(|pr| pr.html_url == *url)
+                    .and_then(|pr| stale_base(&branch, &pr.base.name, &open_prs, cfg));
+            }
+        }
+        Page::New {
+            author,
+            repo,
+            branch,
+            base,
+        } => {
+            let open_prs = if cfg.pr_options.base.is_none() {
+                open_pull_requests(endpoint, author, repo, &cfg.env).await
+            } else {
+                vec![]
+            };
+            *base = base_branch(branch, cfg, &open_prs);
         }
+        Page::NewAtParent { .. } => { /* Stack of local topic branches is not available */ }
     }
+    Ok(page)
+}
+
+// Summary of a pull request printed with --info
+#[derive(PartialEq, Debug, Serialize)]
+pub struct Summary {
+    pub number: usize,
+    pub url: String,
+    pub title: String,
+    pub state: &'static str,
+    pub draft: bool,
+    pub author: String,
+    pub base: String,
+    pub head: String,
+    pub review_decision: Option<&'static str>,
+    pub checks: Option<&'static str>,
+    #[serde(skip)]
+    pub stale_base: Option<StaleBase>,
+}
+
+// Emulate 'reviewDecision' of GraphQL API with REST API. Only the latest review of each reviewer
+// is considered.
+pub fn review_decision(
+    pr: &github_api::PullRequest,
+    reviews: &[github_api::Review],
+) -> Option<&'static str> {
+    let mut latest: Vec<(&str, &str)> = vec![];
+    for review in reviews.iter() {
+        let state = review.state.as_str();
+        if state != "APPROVED" && state != "CHANGES_REQUESTED" && state != "DISMISSED" {
+            continue; // Comments do not change decision
+        }
+        let login = review.user.login.as_str();
+        match latest.iter_mut().find(|(l, _)| *l == login) {
+            Some(entry) => entry.1 = state,
+            None => latest.push((login, state)),
+        }
+    }
+
+    if latest.iter().any(|(_, s)| *s == "CHANGES_REQUESTED") {
+        Some("changes_requested")
+    } else if latest.iter().any(|(_, s)| *s == "APPROVED") {
+        Some("approved")
+    } else if !pr.requested_reviewers.is_empty() {
+        Some("review_required")
+    } else {
+        None
+    }
+}
+
+fn is_failed_run(run: &github_api::CheckRun) -> bool {
+    matches!(
+        run.conclusion.as_deref(),
+        Some("failure") | Some("timed_out") | Some("cancelled") | Some("action_required")
+    )
+}
+
+// URL of the first failed check run or commit status. Check runs are preferred since they are
+// usually GitHub Actions runs
+pub fn failed_run_url<'a>(
+    status: &'a github_api::CombinedStatus,
+    runs: &'a [github_api::CheckRun],
+) -> Option<&'a str> {
+    let run = runs
+        .iter()
+        .filter(|r| r.status == "completed" && is_failed_run(r))
+        .find_map(|r| r.html_url.as_deref());
+    run.or_else(|| {
+        status
+            .statuses
+            .iter()
+            .filter(|s| s.state == "failure" || s.state == "error")
+            .find_map(|s| s.target_url.as_deref())
+    })
+}
+
+// Combine commit statuses and check runs into one of 'failure', 'pending' or 'success'
+pub fn check_status(
+    status: &github_api::CombinedStatus,
+    runs: &[github_api::CheckRun],
+) -> Option<&'static str> {
+    let statuses = if status.total_count == 0 {
+        None
+    } else {
+        Some(status.state.as_str())
+    };
+    let conclusions = runs.iter().map(|r| {
+        if r.status != "completed" {
+            "pending"
+        } else if is_failed_run(r) {
+            "failure"
+        } else {
+            "success"
+        }
+    });
+    let states = statuses.into_iter().chain(conclusions).collect::<Vec<_>>();
+
+    if states.is_empty() {
+        None
+    } else if states.iter().any(|s| *s == "failure" || *s == "error") {
+        Some("failure")
+    } else if states.contains(&"pending") {
+        Some("pending")
+    } else {
+        Some("success")
+    }
+}
+
+// Returns None when no pull request is created for the branch yet
+pub async fn find_summary(
+    endpoint: &str,
+    author: &str,
+    repo: &str,
+    cfg: &Config,
+) -> Result<Option<Summary>> {
+    let (url, stale_base) = match find_page(endpoint, author, repo, cfg).await? {
+        Page::Existing { url, stale_base } => (url, stale_base),
+        _ => return Ok(None),
+    };
+    let (owner, repo, number) = match parse_pr_url(&url) {
+        Some(parsed) => parsed,
+        None => {
+            return Error::err(ErrorKind::BrokenUrl {
+                url,
+                msg: "Not a pull request URL".to_string(),
+            })
+        }
+    };
+
+    let client = github_api::Client::from_env(endpoint, &cfg.env)?;
+    let pr = client.pull_request(owner, repo, number).await?;
+    let (reviews, status, runs) = futures::join!(
+        client.pull_request_reviews(owner, repo, number),
+        client.combined_status(owner, repo, &pr.head.sha),
+        client.check_runs(owner, repo, &pr.head.sha),
+    );
+
+    Ok(Some(Summary {
+        number: pr.number,
+        review_decision: review_decision(&pr, &reviews?),
+        checks: check_status(&status?, &runs?),
+        stale_base,
+        state: if pr.merged {
+            "merged"
+        } else if pr.state == "open" {
+            "open"
+        } else {
+            "closed"
+        },
+        draft: pr.draft,
+        author: pr.user.login,
+        base: pr.base.name,
+        head: pr.head.name,
+        title: pr.title,
+        url: pr.html_url,
+    }))
 }
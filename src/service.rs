@@ -1,12 +1,17 @@
 use crate::async_runtime;
+use crate::bitbucket_api;
 use crate::config::Config;
 use crate::error::{Error, ErrorKind, Result};
 use crate::github_api::Client;
-use crate::page::{DiffOp, Line, Page};
+use crate::gitlab_api;
+use crate::page::{ArchiveFormat, DiffOp, Line, Page, Section, WebIde};
 use crate::pull_request;
+use crate::website;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
 use std::borrow::Cow;
 use std::mem;
-use url::Url;
+use url::{form_urlencoded, Url};
 
 #[cfg(target_os = "windows")]
 fn to_slash(s: &str) -> String {
@@ -63,6 +68,767 @@ fn fetch_homepage(
     async_runtime::blocking(client.repo_homepage(user, repo))
 }
 
+// Returns URL of the issue or pull request only when exactly one is hit
+fn search_single_issue(
+    endpoint: &str,
+    cfg: &Config,
+    text: &str,
+    user: &str,
+    repo: &str,
+) -> Result<Option<String>> {
+    let client = Client::from_env(endpoint, &cfg.env)?;
+    let mut urls = async_runtime::blocking(client.search_issue_urls(text, user, repo, 2))?;
+    Ok(if urls.len() == 1 { urls.pop() } else { None })
+}
+
+// Failed run is looked up only when API token is available since the list of runs is enough
+// without it
+fn find_failed_run_url(
+    endpoint: &str,
+    cfg: &Config,
+    user: &str,
+    repo: &str,
+    hash: &str,
+) -> Result<Option<String>> {
+    let token = if endpoint == "api.github.com" {
+        &cfg.env.github_token
+    } else {
+        &cfg.env.ghe_token
+    };
+    if token.is_none() {
+        return Ok(None);
+    }
+    let client = Client::from_env(endpoint, &cfg.env)?;
+    let (status, runs) = async_runtime::blocking(async {
+        futures::join!(
+            client.combined_status(user, repo, hash),
+            client.check_runs(user, repo, hash),
+        )
+    });
+    Ok(pull_request::failed_run_url(&status?, &runs?).map(str::to_string))
+}
+
+fn find_github_release_url(
+    endpoint: &str,
+    cfg: &Config,
+    user: &str,
+    repo: &str,
+    tag: &str,
+) -> Result<Option<String>> {
+    let client = Client::from_env(endpoint, &cfg.env)?;
+    async_runtime::blocking(client.release_url(user, repo, tag))
+}
+
+fn gitlab_release_exists(
+    host: &str,
+    cfg: &Config,
+    user: &str,
+    repo: &str,
+    tag: &str,
+) -> Result<bool> {
+    let client = gitlab_api::Client::build(host, &cfg.env)?;
+    async_runtime::blocking(client.release_exists(user, repo, tag))
+}
+
+// Pages domain and custom domains are configured per instance and per project
+fn fetch_gitlab_pages_url(
+    host: &str,
+    cfg: &Config,
+    user: &str,
+    repo: &str,
+) -> Result<Option<String>> {
+    let client = gitlab_api::Client::build(host, &cfg.env)?;
+    async_runtime::blocking(client.pages_url(user, repo))
+}
+
+fn find_gitlab_merge_request(
+    host: &str,
+    cfg: &Config,
+    user: &str,
+    repo: &str,
+    branch: &str,
+) -> Result<Option<String>> {
+    let client = gitlab_api::Client::build(host, &cfg.env)?;
+    async_runtime::blocking(client.open_merge_request_url(user, repo, branch))
+}
+
+fn fetch_bitbucket_website(cfg: &Config, user: &str, repo: &str) -> Result<Option<String>> {
+    let client = bitbucket_api::Client::build(&cfg.env)?;
+    async_runtime::blocking(client.website(user, repo))
+}
+
+fn url_with_query(mut url: String, pairs: &[(&str, &str)]) -> String {
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    query.extend_pairs(pairs);
+    append_query(&mut url, query);
+    url
+}
+
+fn append_query(url: &mut String, mut query: form_urlencoded::Serializer<String>) {
+    let query = query.finish();
+    if !query.is_empty() {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&query);
+    }
+}
+
+// Characters such as '#', '&' and '+' are allowed in branch names. Each component separated by '/'
+// is encoded so that the branch name can be embedded in path of URL
+fn encode_branch(branch: &str) -> String {
+    branch
+        .split('/')
+        .map(|s| form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// Reports the first option which is given but cannot be passed to the creation page of the service
+fn check_pr_options(service: &str, options: &[(&'static str, bool)]) -> Result<()> {
+    match options.iter().find(|(_, given)| *given) {
+        Some(&(option, _)) => Error::err(ErrorKind::PullReqOptionNotSupported {
+            option,
+            service: service.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn build_github_new_pr_url(
+    host: &str,
+    author: &str,
+    repo: &str,
+    head: &str,
+    branch: &str,
+    base: Option<String>,
+    cfg: &Config,
+) -> String {
+    let opts = &cfg.pr_options;
+    let mut url = match base {
+        Some(ref base) => format!(
+            "https://{}/{}/{}/compare/{}...{}?expand=1",
+            host,
+            author,
+            repo,
+            encode_branch(base),
+            head,
+        ),
+        None => format!(
+            "https://{}/{}/{}/compare/{}?expand=1",
+            host, author, repo, head
+        ),
+    };
+
+    let desc = pull_request::Description::from_local_commits(branch, base.as_deref(), cfg);
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    if let Some(ref title) = desc.title {
+        query.append_pair("title", title);
+    }
+    if let Some(ref body) = desc.body {
+        query.append_pair("body", body);
+    }
+    if !opts.labels.is_empty() {
+        query.append_pair("labels", &opts.labels.join(","));
+    }
+    if !opts.assignees.is_empty() {
+        query.append_pair("assignees", &opts.assignees.join(","));
+    }
+    if let Some(ref template) = opts.template {
+        query.append_pair("template", template);
+    }
+    append_query(&mut url, query);
+    url
+}
+
+fn push_gitlab_quick_actions(lines: &mut Vec<String>, labels: &[String], assignees: &[String]) {
+    for label in labels.iter() {
+        lines.push(format!("/label ~\"{}\"", label));
+    }
+    for assignee in assignees.iter() {
+        lines.push(format!("/assign @{}", assignee));
+    }
+}
+
+// When diff_path is specified, 'Changes' tab is opened and scrolled to the file. Anchor of the file
+// is SHA-1 of its path. Line cannot be specified since line anchors also need the old line number
+fn gitlab_diff_anchor(path: &str) -> String {
+    format!("{:x}", Sha1::digest(to_slash(path).as_bytes()))
+}
+
+// Open merge request for the branch is searched via API. When API is not available (e.g. offline or
+// private project without token), fall back into 'New merge request' page
+fn build_gitlab_mr_url(
+    host: &str,
+    user: &str,
+    repo: &str,
+    diff_path: Option<&str>,
+    cfg: &Config,
+) -> Result<String> {
+    let branch = pull_request::branch_name(cfg)?;
+    if let Ok(Some(url)) = find_gitlab_merge_request(host, cfg, user, repo, &branch) {
+        return Ok(match diff_path {
+            Some(path) => format!("{}/diffs#{}", url, gitlab_diff_anchor(path)),
+            None => url,
+        });
+    }
+    Ok(build_gitlab_new_mr_url(
+        host, user, repo, &branch, diff_path, cfg,
+    ))
+}
+
+// Labels, assignees and reviewers are set via quick actions in description
+//   https://docs.gitlab.com/ee/user/project/quick_actions.html
+fn build_gitlab_new_mr_url(
+    host: &str,
+    user: &str,
+    repo: &str,
+    branch: &str,
+    diff_path: Option<&str>,
+    cfg: &Config,
+) -> String {
+    let opts = &cfg.pr_options;
+    let base = pull_request::base_branch(branch, cfg, &[]);
+    let desc = pull_request::Description::from_local_commits(branch, base.as_deref(), cfg);
+
+    let mut url = format!("https://{}/{}/{}/-/merge_requests/new", host, user, repo);
+    if diff_path.is_some() {
+        url.push_str("/diffs");
+    }
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    query.append_pair("merge_request[source_branch]", branch);
+    if let Some(ref base) = base {
+        query.append_pair("merge_request[target_branch]", base);
+    }
+
+    let title = match desc.title {
+        Some(title) if opts.draft => Some(format!("Draft: {}", title)),
+        None if opts.draft => Some(format!("Draft: {}", branch)),
+        title => title,
+    };
+    if let Some(ref title) = title {
+        query.append_pair("merge_request[title]", title);
+    }
+
+    let mut lines = vec![];
+    if let Some(body) = desc.body {
+        lines.push(body);
+    }
+    push_gitlab_quick_actions(&mut lines, &opts.labels, &opts.assignees);
+    for reviewer in opts.reviewers.iter() {
+        lines.push(format!("/assign_reviewer @{}", reviewer));
+    }
+    if !lines.is_empty() {
+        query.append_pair("merge_request[description]", &lines.join("\n"));
+    }
+
+    if let Some(ref template) = opts.template {
+        query.append_pair("issuable_template", template);
+    }
+    append_query(&mut url, query);
+    if let Some(path) = diff_path {
+        url.push('#');
+        url.push_str(&gitlab_diff_anchor(path));
+    }
+    url
+}
+
+fn github_section_path(section: Section) -> &'static str {
+    match section {
+        Section::Releases => "releases",
+        Section::Tags => "tags",
+        Section::Branches => "branches",
+        Section::Ci => "actions",
+        Section::Wiki => "wiki",
+        Section::PullRequests => "pulls",
+        Section::Issues => "issues",
+        Section::Milestones => "milestones",
+        Section::Labels => "labels",
+        Section::Security => "security",
+        Section::Insights => "pulse",
+        Section::Forks => "network/members",
+        Section::Settings => "settings",
+    }
+}
+
+// Default branch of the remote in the local repository. 'HEAD' is resolved into the default branch
+// by the service when the local repository is not available (e.g. --repo is specified)
+fn default_branch(cfg: &Config) -> String {
+    let remote = match cfg.remote {
+        Some(ref r) => r,
+        None => return "HEAD".to_string(),
+    };
+    let prefix = format!("{}/", remote);
+    match cfg.git().remote_default_branch(remote) {
+        Ok(branch) => match branch.strip_prefix(&prefix) {
+            Some(b) => b.to_string(),
+            None => branch,
+        },
+        Err(_) => "HEAD".to_string(),
+    }
+}
+
+fn gitlab_section_path(section: Section, cfg: &Config) -> String {
+    let path = match section {
+        Section::Releases => "-/releases",
+        Section::Tags => "-/tags",
+        Section::Branches => "-/branches",
+        Section::Ci => "-/pipelines",
+        Section::Wiki => "-/wikis/home",
+        Section::PullRequests => "-/merge_requests",
+        Section::Issues => "-/issues",
+        Section::Milestones => "-/milestones",
+        Section::Labels => "-/labels",
+        Section::Security => "-/security/dashboard",
+        // Note: Repository graphs are per branch
+        Section::Insights => {
+            let branch = match cfg.branch {
+                Some(ref b) => b.clone(),
+                None => default_branch(cfg),
+            };
+            return format!("-/graphs/{}/charts", branch);
+        }
+        Section::Forks => "-/forks",
+        Section::Settings => "edit",
+    };
+    path.to_string()
+}
+
+fn bitbucket_section_path(section: Section) -> Option<&'static str> {
+    match section {
+        Section::Tags => Some("downloads/?tab=tags"),
+        Section::Branches => Some("branches/"),
+        Section::Ci => Some("pipelines"),
+        Section::Wiki => Some("wiki"),
+        Section::PullRequests => Some("pull-requests/"),
+        Section::Issues => Some("issues"),
+        Section::Forks => Some("forks"),
+        Section::Settings => Some("admin"),
+        Section::Releases
+        | Section::Milestones
+        | Section::Labels
+        | Section::Security
+        | Section::Insights => None,
+    }
+}
+
+// Note: Some sections belong to a project and others belong to a Git repository in the project
+fn azure_devops_section_url(team: &str, repo: &str, section: Section) -> Option<String> {
+    let project = |path| format!("https://dev.azure.com/{}/{}/{}", team, repo, path);
+    let git = |path| format!("https://dev.azure.com/{}/_git/{}/{}", team, repo, path);
+    match section {
+        Section::Releases => Some(project("_release")),
+        Section::Tags => Some(git("tags")),
+        Section::Branches => Some(git("branches")),
+        Section::Ci => Some(project("_build")),
+        Section::Wiki => Some(project("_wiki")),
+        Section::PullRequests => Some(git("pullrequests")),
+        Section::Issues => Some(project("_workitems")),
+        Section::Settings => Some(project("_settings/repositories")),
+        Section::Milestones
+        | Section::Labels
+        | Section::Security
+        | Section::Insights
+        | Section::Forks => None,
+    }
+}
+
+fn section_not_supported(section: Section, service: &str) -> Result<String> {
+    Error::err(ErrorKind::SectionNotSupported {
+        section,
+        service: service.to_string(),
+    })
+}
+
+fn web_ide_not_supported(ide: WebIde, service: &str) -> Result<String> {
+    Error::err(ErrorKind::WebIdeNotSupported {
+        ide,
+        service: service.to_string(),
+    })
+}
+
+// Gitpod opens a workspace from the URL of file page
+//   https://www.gitpod.io/docs/context-urls
+fn gitpod_url(file_url: String) -> String {
+    format!("https://gitpod.io/#{}", file_url)
+}
+
+fn github_line_anchor(line: &Option<Line>) -> String {
+    match line {
+        None => "".to_string(),
+        Some(Line::At(line)) => format!("#L{}", line),
+        Some(Line::Range(start, end)) => format!("#L{}-L{}", start, end),
+    }
+}
+
+fn log_option_not_supported(option: &'static str, service: &str) -> Result<String> {
+    Error::err(ErrorKind::LogOptionNotSupported {
+        option,
+        service: service.to_string(),
+    })
+}
+
+// Commit history page such as 'commits/{hash}/{path}'. Names of query parameters for author, since
+// and until filters are given. None means that the filter is not supported by the service
+fn build_history_url(
+    mut url: String,
+    relative_path: &Option<String>,
+    names: [Option<&str>; 3],
+    cfg: &Config,
+    service: &str,
+) -> Result<String> {
+    if let Some(path) = relative_path {
+        url = format!("{}/{}", url, to_slash(path));
+    }
+    let opts = &cfg.log_options;
+    let filters = [
+        ("--author", &opts.author),
+        ("--since", &opts.since),
+        ("--until", &opts.until),
+    ];
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    for (&(option, value), name) in filters.iter().zip(names.iter()) {
+        match (value, name) {
+            (Some(value), Some(name)) => {
+                query.append_pair(name, value);
+            }
+            (Some(_), None) => return log_option_not_supported(option, service),
+            (None, _) => {}
+        }
+    }
+    append_query(&mut url, query);
+    Ok(url)
+}
+
+// Qualifiers of code search query are appended to the query
+fn code_search_query(query: &str, qualifiers: &[(&str, &Option<String>)]) -> String {
+    let mut q = query.to_string();
+    for (name, value) in qualifiers {
+        if let Some(value) = value {
+            q.push_str(&format!(" {}:{}", name, value));
+        }
+    }
+    q
+}
+
+fn search_option_not_supported(option: &'static str, service: &str) -> Result<String> {
+    Error::err(ErrorKind::SearchOptionNotSupported {
+        option,
+        service: service.to_string(),
+    })
+}
+
+const DEFAULT_SOURCEGRAPH_URL: &str = "https://sourcegraph.com";
+
+// Note: Remove port number since Sourcegraph names a repository with its host name
+fn sourcegraph_repo_name(host: &str, user: &str, repo: &str) -> String {
+    let host = host.split(':').next().unwrap();
+    format!("{}/{}/{}", host, user, repo)
+}
+
+// Sourcegraph is used when --sourcegraph is specified or the host is listed in
+// $GIT_BRWS_SOURCEGRAPH_HOSTS
+fn sourcegraph_url_for<'a>(host: &str, cfg: &'a Config) -> Option<&'a str> {
+    let env = &cfg.env;
+    if cfg.sourcegraph || env.sourcegraph_hosts.iter().any(|h| h == host) {
+        Some(
+            env.sourcegraph_url
+                .as_deref()
+                .unwrap_or(DEFAULT_SOURCEGRAPH_URL),
+        )
+    } else {
+        None
+    }
+}
+
+// Returns None when the page has no equivalent on Sourcegraph
+//   https://docs.sourcegraph.com/getting-started/github-vs-sourcegraph
+fn build_sourcegraph_url(
+    sourcegraph_url: &str,
+    host: &str,
+    user: &str,
+    repo: &str,
+    cfg: &Config,
+    page: &Page,
+) -> Option<String> {
+    let base = format!(
+        "{}/{}",
+        sourcegraph_url.trim_end_matches('/'),
+        sourcegraph_repo_name(host, user, repo),
+    );
+    match page {
+        // Note: Sourcegraph has no blame page
+        Page::FileOrDir { blame: true, .. } => None,
+        Page::Open {
+            website: false,
+            pull_request: false,
+        } => Some(match cfg.branch {
+            Some(ref branch) => format!("{}@{}", base, branch),
+            None => base,
+        }),
+        Page::FileOrDir {
+            ref relative_path,
+            ref hash,
+            ref line,
+            ..
+        } => {
+            // Note: Sourcegraph distinguishes files and directories in its URL
+            let is_dir = cfg
+                .git()
+                .root_dir()
+                .map(|root| root.join(relative_path).is_dir())
+                .unwrap_or(false);
+            Some(format!(
+                "{}@{}/-/{}/{}{}",
+                base,
+                hash,
+                if is_dir { "tree" } else { "blob" },
+                to_slash(relative_path),
+                match line {
+                    None => "".to_string(),
+                    Some(Line::At(line)) => format!("?L{}", line),
+                    Some(Line::Range(start, end)) => format!("?L{}-{}", start, end),
+                },
+            ))
+        }
+        Page::Commit { ref hash } => Some(format!("{}/-/commit/{}", base, hash)),
+        Page::Diff {
+            ref lhs,
+            ref rhs,
+            op,
+        } => Some(format!("{}/-/compare/{}{}{}", base, lhs, op, rhs)),
+        _ => None,
+    }
+}
+
+//   https://docs.sourcegraph.com/code_search/reference/queries
+fn build_sourcegraph_search_url(
+    sourcegraph_url: &str,
+    host: &str,
+    user: &str,
+    repo: &str,
+    query: &str,
+    cfg: &Config,
+) -> String {
+    let mut repo_filter = format!(
+        "repo:^{}$",
+        regex::escape(&sourcegraph_repo_name(host, user, repo))
+    );
+    if let Some(ref branch) = cfg.branch {
+        repo_filter.push('@');
+        repo_filter.push_str(branch);
+    }
+    let opts = &cfg.search_options;
+    let q = code_search_query(
+        &format!("{} {}", repo_filter, query),
+        &[("file", &opts.path), ("lang", &opts.language)],
+    );
+    url_with_query(
+        format!("{}/search", sourcegraph_url.trim_end_matches('/')),
+        &[("q", &q)],
+    )
+}
+
+fn build_gitlab_code_search_url(
+    host: &str,
+    user: &str,
+    repo: &str,
+    query: &str,
+    cfg: &Config,
+) -> Result<String> {
+    let opts = &cfg.search_options;
+    if opts.language.is_some() {
+        return search_option_not_supported("--lang", host);
+    }
+
+    // Note: Search page under the project path is used since global search page requires numeric
+    // project ID which is only available via API
+    let q = code_search_query(query, &[("path", &opts.path)]);
+    let mut pairs = vec![("search", q.as_str()), ("scope", "blobs")];
+    if let Some(ref branch) = cfg.branch {
+        pairs.push(("repository_ref", branch));
+    }
+    Ok(url_with_query(
+        format!("https://{}/{}/{}/-/search", host, user, repo),
+        &pairs,
+    ))
+}
+
+// Body of a new issue. Permalink to the file and the commit hash are appended to the body given
+// by --body
+fn new_issue_body(
+    cfg: &Config,
+    file: &Option<Box<Page>>,
+    commit: &Option<String>,
+) -> Result<Option<String>> {
+    let mut paragraphs = vec![];
+    if let Some(ref body) = cfg.issue_options.body {
+        paragraphs.push(body.clone());
+    }
+    // Note: Permalink in the body always points to the hosting service even if the host is listed
+    // in $GIT_BRWS_SOURCEGRAPH_HOSTS
+    if let Some(ref file) = file {
+        paragraphs.push(build_url(file, cfg, &mut vec![], false)?);
+    }
+    if let (Some(_), Some(ref commit)) = (file, commit) {
+        paragraphs.push(format!("Commit: {}", commit));
+    }
+    if paragraphs.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(paragraphs.join("\n\n")))
+    }
+}
+
+//   https://help.github.com/en/github/managing-your-work-on-github/about-automation-for-issues-and-pull-requests-with-query-parameters
+fn build_github_new_issue_url(
+    host: &str,
+    user: &str,
+    repo: &str,
+    body: Option<String>,
+    cfg: &Config,
+) -> String {
+    let opts = &cfg.issue_options;
+    let mut url = format!("https://{}/{}/{}/issues/new", host, user, repo);
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    if let Some(ref title) = opts.title {
+        query.append_pair("title", title);
+    }
+    if let Some(ref body) = body {
+        query.append_pair("body", body);
+    }
+    if !opts.labels.is_empty() {
+        query.append_pair("labels", &opts.labels.join(","));
+    }
+    if !opts.assignees.is_empty() {
+        query.append_pair("assignees", &opts.assignees.join(","));
+    }
+    if let Some(ref template) = opts.template {
+        query.append_pair("template", template);
+    }
+    append_query(&mut url, query);
+    url
+}
+
+fn build_gitlab_new_issue_url(
+    host: &str,
+    user: &str,
+    repo: &str,
+    body: Option<String>,
+    cfg: &Config,
+) -> String {
+    let opts = &cfg.issue_options;
+    let mut url = format!("https://{}/{}/{}/-/issues/new", host, user, repo);
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    if let Some(ref title) = opts.title {
+        query.append_pair("issue[title]", title);
+    }
+
+    let mut lines = vec![];
+    if let Some(body) = body {
+        lines.push(body);
+    }
+    push_gitlab_quick_actions(&mut lines, &opts.labels, &opts.assignees);
+    if !lines.is_empty() {
+        query.append_pair("issue[description]", &lines.join("\n"));
+    }
+
+    if let Some(ref template) = opts.template {
+        query.append_pair("issuable_template", template);
+    }
+    append_query(&mut url, query);
+    url
+}
+
+// GitHub identifies a file in diff with SHA-256 hash of its path. 'R' means right side of diff
+fn github_diff_anchor(relative_path: &str, line: &Option<Line>) -> String {
+    let path = to_slash(relative_path);
+    let hash = Sha256::digest(path.as_bytes());
+    match line {
+        None => format!("diff-{:x}", hash),
+        Some(Line::At(line)) => format!("diff-{:x}R{}", hash, line),
+        Some(Line::Range(start, end)) => format!("diff-{:x}R{}-R{}", hash, start, end),
+    }
+}
+
+// When diff_anchor is specified, the page is scrolled to the file in 'Files changed' tab.
+// 'Create Pull Request' page also shows the diff with the same anchors.
+fn build_github_pr_url(
+    host: &str,
+    user: &str,
+    repo: &str,
+    api_endpoint: Option<impl AsRef<str>>,
+    diff_anchor: Option<&str>,
+    cfg: &Config,
+    warnings: &mut Vec<String>,
+) -> Result<String> {
+    let endpoint = match api_endpoint {
+        Some(e) => e,
+        None => {
+            return Error::err(ErrorKind::PullReqNotSupported {
+                service: host.to_string(),
+            })
+        }
+    };
+
+    // Note: Draft and reviewers cannot be specified via query parameters on GitHub. They are
+    // reported before accessing API
+    //   https://help.github.com/en/github/managing-your-work-on-github/about-automation-for-issues-and-pull-requests-with-query-parameters
+    let opts = &cfg.pr_options;
+    check_pr_options(
+        host,
+        &[
+            ("--draft", opts.draft),
+            ("--reviewer", !opts.reviewers.is_empty()),
+        ],
+    )?;
+
+    let page = pull_request::find_page(endpoint.as_ref(), user, repo, cfg);
+    let url = match async_runtime::blocking(page)? {
+        pull_request::Page::Existing { url, stale_base } => {
+            if let Some(stale_base) = stale_base {
+                warnings.push(stale_base.to_string());
+            }
+            return Ok(match diff_anchor {
+                Some(anchor) => format!("{}/files#{}", url, anchor),
+                None => url,
+            });
+        }
+        pull_request::Page::New {
+            author,
+            repo,
+            branch,
+            base,
+        } => build_github_new_pr_url(
+            host,
+            &author,
+            &repo,
+            &encode_branch(&branch),
+            &branch,
+            base,
+            cfg,
+        ),
+        pull_request::Page::NewAtParent {
+            author,
+            repo,
+            fork_author,
+            branch,
+        } => build_github_new_pr_url(
+            host,
+            &author,
+            &repo,
+            &format!("{}:{}", fork_author, encode_branch(&branch)),
+            &branch,
+            // Note: Stack of local topic branches is not available at parent repository
+            cfg.pr_options.base.clone(),
+            cfg,
+        ),
+    };
+
+    Ok(match diff_anchor {
+        Some(anchor) => format!("{}#{}", url, anchor),
+        None => url,
+    })
+}
+
 fn build_github_like_url(
     host: &str,
     user: &str,
@@ -70,6 +836,7 @@ fn build_github_like_url(
     api_endpoint: Option<impl AsRef<str>>,
     cfg: &Config,
     page: &Page,
+    warnings: &mut Vec<String>,
 ) -> Result<String> {
     match page {
         Page::Open { website: true, .. } => {
@@ -122,38 +889,33 @@ fn build_github_like_url(
         }
         Page::Open {
             pull_request: true, ..
+        } => build_github_pr_url(host, user, repo, api_endpoint, None, cfg, warnings),
+        // Note: Fork is specified with '{owner}:{repo}:{branch}' form
+        Page::CompareUpstream {
+            base,
+            user: fork_user,
+            repo: fork_repo,
+            branch,
         } => {
-            if let Some(endpoint) = api_endpoint {
-                match async_runtime::blocking(pull_request::find_page(
-                    endpoint.as_ref(),
+            let head = format!("{}:{}:{}", fork_user, fork_repo, encode_branch(branch));
+            Ok(match base {
+                Some(base) => format!(
+                    "https://{}/{}/{}/compare/{}...{}",
+                    host,
                     user,
                     repo,
-                    cfg,
-                ))? {
-                    pull_request::Page::Existing { url } => Ok(url),
-                    pull_request::Page::New {
-                        author,
-                        repo,
-                        branch,
-                    } => Ok(format!(
-                        "https://{}/{}/{}/compare/{}?expand=1",
-                        host, author, repo, branch,
-                    )),
-                    pull_request::Page::NewAtParent {
-                        author,
-                        repo,
-                        fork_author,
-                        branch,
-                    } => Ok(format!(
-                        "https://{}/{}/{}/compare/{}:{}?expand=1",
-                        host, author, repo, fork_author, branch,
-                    )),
-                }
-            } else {
-                Error::err(ErrorKind::PullReqNotSupported {
-                    service: host.to_string(),
-                })
-            }
+                    encode_branch(base),
+                    head
+                ),
+                None => format!("https://{}/{}/{}/compare/{}", host, user, repo, head),
+            })
+        }
+        Page::PullRequestFile {
+            ref relative_path,
+            ref line,
+        } => {
+            let anchor = github_diff_anchor(relative_path, line);
+            build_github_pr_url(host, user, repo, api_endpoint, Some(&anchor), cfg, warnings)
         }
         Page::Open { .. } => {
             if let Some(ref b) = cfg.branch {
@@ -162,10 +924,21 @@ fn build_github_like_url(
                 Ok(format!("https://{}/{}/{}", host, user, repo))
             }
         }
-        Page::Tag { ref tagname, .. } => Ok(format!(
-            "https://{}/{}/{}/tree/{}",
-            host, user, repo, tagname,
-        )),
+        // Note: Release page is opened when a release is published for the tag. Tags without
+        // release and failures of API request fall back to the tree of the tag
+        Page::Tag { ref tagname, .. } => {
+            if let Some(endpoint) = api_endpoint {
+                if let Ok(Some(url)) =
+                    find_github_release_url(endpoint.as_ref(), cfg, user, repo, tagname)
+                {
+                    return Ok(url);
+                }
+            }
+            Ok(format!(
+                "https://{}/{}/{}/tree/{}",
+                host, user, repo, tagname,
+            ))
+        }
         Page::Diff {
             ref lhs,
             ref rhs,
@@ -191,16 +964,199 @@ fn build_github_like_url(
             feat = if *blame { "blame" } else { "blob" },
             hash = hash,
             path = to_slash(relative_path),
-            anchor = match line {
-                None => "".to_string(),
-                Some(Line::At(line)) => format!("#L{}", line),
-                Some(Line::Range(start, end)) => format!("#L{}-L{}", start, end),
-            },
+            anchor = github_line_anchor(line),
         )),
+        // Note: github.dev, vscode.dev and Codespaces are available only for github.com
+        Page::EditFile {
+            ref relative_path,
+            ref branch,
+            ref line,
+            ide,
+        } => {
+            let path = to_slash(relative_path);
+            match ide {
+                None => Ok(format!(
+                    "https://{}/{}/{}/edit/{}/{}",
+                    host, user, repo, branch, path
+                )),
+                Some(WebIde::Gitpod) => Ok(gitpod_url(format!(
+                    "https://{}/{}/{}/blob/{}/{}",
+                    host, user, repo, branch, path
+                ))),
+                Some(ide) if host != "github.com" => web_ide_not_supported(*ide, host),
+                Some(WebIde::GitHubDev) => Ok(format!(
+                    "https://github.dev/{}/{}/blob/{}/{}{}",
+                    user,
+                    repo,
+                    branch,
+                    path,
+                    github_line_anchor(line),
+                )),
+                Some(WebIde::VsCodeDev) => Ok(format!(
+                    "https://vscode.dev/github/{}/{}/blob/{}/{}{}",
+                    user,
+                    repo,
+                    branch,
+                    path,
+                    github_line_anchor(line),
+                )),
+                Some(WebIde::Codespaces) => Ok(url_with_query(
+                    format!("https://codespaces.new/{}/{}", user, repo),
+                    &[("ref", branch)],
+                )),
+            }
+        }
         Page::Issue { number } => Ok(format!(
             "https://{}/{}/{}/issues/{}",
             host, user, repo, number
         )),
+        Page::Section { section } => Ok(format!(
+            "https://{}/{}/{}/{}",
+            host,
+            user,
+            repo,
+            github_section_path(*section),
+        )),
+        // Note: On GitHub Enterprise, raw URL on the host serves content of LFS objects as well
+        Page::RawFile {
+            ref relative_path,
+            ref hash,
+            lfs,
+        } => Ok(match host {
+            "github.com" if *lfs => format!(
+                "https://media.githubusercontent.com/media/{}/{}/{}/{}",
+                user,
+                repo,
+                hash,
+                to_slash(relative_path),
+            ),
+            "github.com" => format!(
+                "https://raw.githubusercontent.com/{}/{}/{}/{}",
+                user,
+                repo,
+                hash,
+                to_slash(relative_path),
+            ),
+            _ => format!(
+                "https://{}/{}/{}/raw/{}/{}",
+                host,
+                user,
+                repo,
+                hash,
+                to_slash(relative_path),
+            ),
+        }),
+        Page::Archive {
+            ref name,
+            tag,
+            format,
+        } => Ok(if *tag {
+            format!(
+                "https://{}/{}/{}/archive/refs/tags/{}.{}",
+                host,
+                user,
+                repo,
+                name,
+                format.extension(),
+            )
+        } else {
+            format!(
+                "https://{}/{}/{}/archive/{}.{}",
+                host,
+                user,
+                repo,
+                name,
+                format.extension(),
+            )
+        }),
+        Page::History {
+            ref relative_path,
+            ref hash,
+        } => build_history_url(
+            format!("https://{}/{}/{}/commits/{}", host, user, repo, hash),
+            relative_path,
+            [Some("author"), Some("since"), Some("until")],
+            cfg,
+            host,
+        ),
+        Page::Ci {
+            ref hash,
+            ref branch,
+        } => {
+            if let Some(endpoint) = api_endpoint {
+                if let Ok(Some(url)) = find_failed_run_url(endpoint.as_ref(), cfg, user, repo, hash)
+                {
+                    return Ok(url);
+                }
+            }
+            // Note: Workflow runs cannot be filtered by commit on web. Checks page of the commit
+            // is used instead
+            match branch {
+                Some(branch) => Ok(url_with_query(
+                    format!("https://{}/{}/{}/actions", host, user, repo),
+                    &[("query", &format!("branch:{}", branch))],
+                )),
+                None => Ok(format!(
+                    "https://{}/{}/{}/commit/{}/checks",
+                    host, user, repo, hash
+                )),
+            }
+        }
+        Page::NewIssue {
+            ref file,
+            ref commit,
+        } => {
+            let body = new_issue_body(cfg, file, commit)?;
+            Ok(build_github_new_issue_url(host, user, repo, body, cfg))
+        }
+        // Note: Only default branch is searchable on GitHub
+        Page::CodeSearch { ref query } => {
+            if cfg.branch.is_some() {
+                return search_option_not_supported("--branch", host);
+            }
+            let opts = &cfg.search_options;
+            let q = code_search_query(
+                &format!("repo:{}/{} {}", user, repo, query),
+                &[("path", &opts.path), ("language", &opts.language)],
+            );
+            Ok(url_with_query(
+                format!("https://{}/search", host),
+                &[("q", &q), ("type", "code")],
+            ))
+        }
+        Page::IssueSearch { ref text } => {
+            // Note: Fall back into search results page when API is not available
+            if let Some(endpoint) = api_endpoint {
+                if let Ok(Some(url)) = search_single_issue(endpoint.as_ref(), cfg, text, user, repo)
+                {
+                    return Ok(url);
+                }
+            }
+            Ok(url_with_query(
+                format!("https://{}/{}/{}/issues", host, user, repo),
+                &[("q", text)],
+            ))
+        }
+        Page::PullRequest { number } => Ok(format!(
+            "https://{}/{}/{}/pull/{}",
+            host, user, repo, number
+        )),
+        // Note: GitHub has no page for milestone name. Open issues filtered by the milestone instead
+        Page::Milestone { ref name } => Ok(url_with_query(
+            format!("https://{}/{}/{}/issues", host, user, repo),
+            &[("q", &format!("milestone:\"{}\"", name))],
+        )),
+        Page::Epic { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "epic",
+            service: host.to_string(),
+        }),
+        Page::Snippet { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "snippet",
+            service: host.to_string(),
+        }),
+        Page::Qualified { .. } | Page::TrackerIssue { .. } | Page::Package { .. } => {
+            unreachable!("Page is resolved by build_page_url: {:?}", page)
+        }
     }
 }
 
@@ -211,17 +1167,194 @@ fn build_gitlab_url(
     cfg: &Config,
     page: &Page,
 ) -> Result<String> {
-    if let Page::Diff { op, .. } = page {
-        if *op == DiffOp::TwoDots {
-            return Error::err(ErrorKind::GitLabDiffNotSupported);
+    match page {
+        Page::Diff {
+            op: DiffOp::TwoDots,
+            ..
+        } => return Error::err(ErrorKind::GitLabDiffNotSupported),
+        Page::Open {
+            website: false,
+            pull_request: true,
+        } => return build_gitlab_mr_url(host, user, repo, None, cfg),
+        Page::PullRequestFile {
+            ref relative_path, ..
+        } => return build_gitlab_mr_url(host, user, repo, Some(relative_path), cfg),
+        // Compare page of the fork project. Base branch is taken from the upstream project
+        Page::CompareUpstream {
+            base,
+            user: fork_user,
+            repo: fork_repo,
+            branch,
+        } => {
+            let base = base
+                .as_ref()
+                .ok_or_else(|| Error::new(ErrorKind::NoUpstreamDefaultBranch))?;
+            let client = gitlab_api::Client::build(host, &cfg.env)?;
+            let id = async_runtime::blocking(client.project_id(user, repo))?;
+            return Ok(url_with_query(
+                format!(
+                    "https://{}/{}/{}/-/compare/{}...{}",
+                    host,
+                    fork_user,
+                    fork_repo,
+                    encode_branch(base),
+                    encode_branch(branch),
+                ),
+                &[("from_project_id", &id.to_string())],
+            ));
+        }
+        // Note: Fall back into the URL guessed from the host when Pages API is not available
+        Page::Open { website: true, .. } => {
+            if let Ok(Some(url)) = fetch_gitlab_pages_url(host, cfg, user, repo) {
+                return Ok(url);
+            }
+        }
+        Page::PullRequest { number } => {
+            return Ok(format!(
+                "https://{}/{}/{}/-/merge_requests/{}",
+                host, user, repo, number
+            ))
+        }
+        // Note: Epics belong to a group, not to a project
+        Page::Epic { number } => {
+            return Ok(format!(
+                "https://{}/groups/{}/-/epics/{}",
+                host, user, number
+            ))
+        }
+        Page::Milestone { ref name } => {
+            return Ok(url_with_query(
+                format!("https://{}/{}/{}/-/milestones", host, user, repo),
+                &[("search_title", name), ("state", "all")],
+            ));
+        }
+        Page::Snippet { number } => {
+            return Ok(format!(
+                "https://{}/{}/{}/-/snippets/{}",
+                host, user, repo, number
+            ))
+        }
+        Page::NewIssue {
+            ref file,
+            ref commit,
+        } => {
+            let body = new_issue_body(cfg, file, commit)?;
+            return Ok(build_gitlab_new_issue_url(host, user, repo, body, cfg));
+        }
+        Page::CodeSearch { ref query } => {
+            return build_gitlab_code_search_url(host, user, repo, query, cfg)
+        }
+        Page::Section { section } => {
+            return Ok(format!(
+                "https://{}/{}/{}/{}",
+                host,
+                user,
+                repo,
+                gitlab_section_path(*section, cfg),
+            ))
+        }
+        Page::Tag { ref tagname, .. } => {
+            if let Ok(true) = gitlab_release_exists(host, cfg, user, repo, tagname) {
+                return Ok(format!(
+                    "https://{}/{}/{}/-/releases/{}",
+                    host, user, repo, tagname
+                ));
+            }
+        }
+        // Note: Files are edited in Web IDE on GitLab
+        Page::EditFile {
+            ref relative_path,
+            ref branch,
+            ide,
+            ..
+        } => {
+            let path = to_slash(relative_path);
+            return match ide {
+                None => Ok(format!(
+                    "https://{}/-/ide/project/{}/{}/edit/{}/-/{}",
+                    host, user, repo, branch, path
+                )),
+                Some(WebIde::Gitpod) => Ok(gitpod_url(format!(
+                    "https://{}/{}/{}/-/blob/{}/{}",
+                    host, user, repo, branch, path
+                ))),
+                Some(ide) => web_ide_not_supported(*ide, host),
+            };
+        }
+        // Note: Raw endpoint of GitLab serves content of LFS objects as well
+        Page::RawFile {
+            ref relative_path,
+            ref hash,
+            ..
+        } => {
+            return Ok(format!(
+                "https://{}/{}/{}/-/raw/{}/{}",
+                host,
+                user,
+                repo,
+                hash,
+                to_slash(relative_path),
+            ))
+        }
+        // Note: Archive name is prefixed with repository name
+        Page::Archive {
+            ref name, format, ..
+        } => {
+            return Ok(format!(
+                "https://{}/{}/{}/-/archive/{}/{}-{}.{}",
+                host,
+                user,
+                repo,
+                name,
+                repo,
+                name.replace('/', "-"),
+                format.extension(),
+            ))
+        }
+        Page::History {
+            ref relative_path,
+            ref hash,
+        } => {
+            return build_history_url(
+                format!("https://{}/{}/{}/-/commits/{}", host, user, repo, hash),
+                relative_path,
+                [Some("author"), None, None],
+                cfg,
+                host,
+            )
+        }
+        Page::Ci { ref hash, .. } => {
+            return Ok(url_with_query(
+                format!("https://{}/{}/{}/-/pipelines", host, user, repo),
+                &[("sha", hash)],
+            ))
+        }
+        Page::IssueSearch { ref text } => {
+            return Ok(url_with_query(
+                format!("https://{}/{}/{}/-/issues", host, user, repo),
+                &[("search", text)],
+            ));
         }
+        _ => {}
     }
-    build_github_like_url(host, user, repo, Option::<&str>::None, cfg, page)
+    // Note: Pull requests are not searched without API endpoint so no warning is reported
+    build_github_like_url(
+        host,
+        user,
+        repo,
+        Option::<&str>::None,
+        cfg,
+        page,
+        &mut vec![],
+    )
 }
 
 fn build_bitbucket_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
     match page {
         Page::Open { website: true, .. } => {
+            if let Ok(Some(url)) = fetch_bitbucket_website(cfg, user, repo) {
+                return Ok(url);
+            }
             // Build bitbucket cloud URL:
             //   https://confluence.atlassian.com/bitbucket/publishing-a-website-on-bitbucket-cloud-221449776.html
             let with_user = format!("https://{}.bitbucket.io/{}", user, repo);
@@ -232,9 +1365,28 @@ fn build_bitbucket_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Res
                 &cfg.env.https_proxy,
             ))
         }
+        // Note: Source and destination are separated with CR ('%0D')
+        Page::CompareUpstream {
+            base: Some(base),
+            user: fork_user,
+            repo: fork_repo,
+            branch,
+        } => Ok(format!(
+            "https://bitbucket.org/{}/{}/branches/compare/{}/{}:{}%0D{}/{}:{}",
+            fork_user,
+            fork_repo,
+            fork_user,
+            fork_repo,
+            encode_branch(branch),
+            user,
+            repo,
+            encode_branch(base),
+        )),
+        Page::CompareUpstream { base: None, .. } => Error::err(ErrorKind::NoUpstreamDefaultBranch),
         Page::Open {
             pull_request: true, ..
-        } => Error::err(ErrorKind::PullReqNotSupported {
+        }
+        | Page::PullRequestFile { .. } => Error::err(ErrorKind::PullReqNotSupported {
             service: "bitbucket.org".to_string(),
         }),
         Page::Open { .. } => {
@@ -280,22 +1432,186 @@ fn build_bitbucket_url(user: &str, repo: &str, cfg: &Config, page: &Page) -> Res
             "https://bitbucket.org/{}/{}/issues/{}",
             user, repo, number,
         )),
+        Page::PullRequest { number } => Ok(format!(
+            "https://bitbucket.org/{}/{}/pull-requests/{}",
+            user, repo, number,
+        )),
+        Page::Section { section } => match bitbucket_section_path(*section) {
+            Some(path) => Ok(format!("https://bitbucket.org/{}/{}/{}", user, repo, path)),
+            None => section_not_supported(*section, "bitbucket.org"),
+        },
+        Page::EditFile {
+            ref relative_path,
+            ref branch,
+            ide,
+            ..
+        } => {
+            let url = format!(
+                "https://bitbucket.org/{}/{}/src/{}/{}",
+                user,
+                repo,
+                branch,
+                to_slash(relative_path),
+            );
+            match ide {
+                None => Ok(url_with_query(url, &[("mode", "edit"), ("at", branch)])),
+                Some(WebIde::Gitpod) => Ok(gitpod_url(url)),
+                Some(ide) => web_ide_not_supported(*ide, "bitbucket.org"),
+            }
+        }
+        // Note: Raw endpoint of Bitbucket serves content of LFS objects as well
+        Page::RawFile {
+            ref relative_path,
+            ref hash,
+            ..
+        } => Ok(format!(
+            "https://bitbucket.org/{}/{}/raw/{}/{}",
+            user,
+            repo,
+            hash,
+            to_slash(relative_path),
+        )),
+        Page::Archive {
+            ref name, format, ..
+        } => Ok(format!(
+            "https://bitbucket.org/{}/{}/get/{}.{}",
+            user,
+            repo,
+            name,
+            format.extension(),
+        )),
+        Page::History {
+            ref relative_path,
+            ref hash,
+        } => {
+            // Note: 'commits/branch/{name}' only accepts branch names. Commits from the commit are
+            // listed at 'commits/{hash}'. Revisions other than branches are resolved into full
+            // hashes by the parser
+            let url = if relative_path.is_some() {
+                format!(
+                    "https://bitbucket.org/{}/{}/history-node/{}",
+                    user, repo, hash
+                )
+            } else if is_commit_hash(hash) {
+                format!("https://bitbucket.org/{}/{}/commits/{}", user, repo, hash)
+            } else {
+                format!(
+                    "https://bitbucket.org/{}/{}/commits/branch/{}",
+                    user, repo, hash
+                )
+            };
+            build_history_url(url, relative_path, [None, None, None], cfg, "bitbucket.org")
+        }
+        // Note: Pipelines cannot be filtered by commit. Commit page shows its builds instead
+        Page::Ci {
+            ref hash,
+            ref branch,
+        } => match branch {
+            Some(branch) => Ok(format!(
+                "https://bitbucket.org/{}/{}/pipelines/results/branch/{}/page/1",
+                user, repo, branch
+            )),
+            None => Ok(format!(
+                "https://bitbucket.org/{}/{}/commits/{}",
+                user, repo, hash
+            )),
+        },
+        // Note: Only main branch is searchable on Bitbucket
+        Page::CodeSearch { ref query } => {
+            if cfg.branch.is_some() {
+                return search_option_not_supported("--branch", "bitbucket.org");
+            }
+            let opts = &cfg.search_options;
+            let q = code_search_query(
+                &format!("repo:{}/{} {}", user, repo, query),
+                &[("path", &opts.path), ("lang", &opts.language)],
+            );
+            Ok(url_with_query(
+                "https://bitbucket.org/search".to_string(),
+                &[("q", &q)],
+            ))
+        }
+        // Note: Bitbucket does not support filling the form of a new issue via query parameters
+        Page::NewIssue { .. } => Ok(format!(
+            "https://bitbucket.org/{}/{}/issues/new",
+            user, repo
+        )),
+        Page::IssueSearch { ref text } => Ok(url_with_query(
+            format!("https://bitbucket.org/{}/{}/issues", user, repo),
+            &[("q", text)],
+        )),
+        Page::Epic { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "epic",
+            service: "bitbucket.org".to_string(),
+        }),
+        Page::Milestone { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "milestone",
+            service: "bitbucket.org".to_string(),
+        }),
+        Page::Snippet { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "snippet",
+            service: "bitbucket.org".to_string(),
+        }),
+        Page::Qualified { .. } | Page::TrackerIssue { .. } | Page::Package { .. } => {
+            unreachable!("Page is resolved by build_page_url: {:?}", page)
+        }
     }
 }
 
+// When diff_path is specified, 'Files' tab is opened with the file selected. Line cannot be specified
+fn build_azure_devops_new_pr_url(
+    team: &str,
+    repo: &str,
+    diff_path: Option<&str>,
+    cfg: &Config,
+) -> Result<String> {
+    let b = match cfg.branch {
+        Some(ref b) => b,
+        None => {
+            return Error::err(ErrorKind::NoLocalRepoFound {
+                operation: "opening a pull request without specifying branch".to_string(),
+            })
+        }
+    };
+
+    // Note: Only source and target branches can be given to the creation page
+    let opts = &cfg.pr_options;
+    check_pr_options(
+        "dev.azure.com",
+        &[
+            ("--draft", opts.draft),
+            ("--label", !opts.labels.is_empty()),
+            ("--reviewer", !opts.reviewers.is_empty()),
+            ("--assignee", !opts.assignees.is_empty()),
+            ("--template", opts.template.is_some()),
+        ],
+    )?;
+
+    let base = pull_request::base_branch(b, cfg, &[]);
+    let base = base.as_deref().unwrap_or("master");
+    let url = format!(
+        "https://dev.azure.com/{}/_git/{}/pullrequestcreate",
+        team, repo
+    );
+    Ok(match diff_path {
+        Some(path) => url_with_query(
+            url,
+            &[
+                ("sourceRef", b),
+                ("targetRef", base),
+                ("_a", "files"),
+                ("path", &format!("/{}", to_slash(path))),
+            ],
+        ),
+        None => url_with_query(url, &[("sourceRef", b), ("targetRef", base)]),
+    })
+}
+
 fn build_azure_devops_url(team: &str, repo: &str, cfg: &Config, page: &Page) -> Result<String> {
     match page {
         Page::Open {
             pull_request: true, ..
-        } => {
-            if let Some(ref b) = cfg.branch {
-                Ok(format!("https://dev.azure.com/{}/_git/{}/pullrequestcreate?sourceRef={}&targetRef=master", team, repo, b))
-            } else {
-                Error::err(ErrorKind::NoLocalRepoFound {
-                    operation: "opening a pull request without specifying branch".to_string(),
-                })
-            }
-        }
+        } => build_azure_devops_new_pr_url(team, repo, None, cfg),
         Page::Open { .. } => {
             if let Some(ref b) = cfg.branch {
                 Ok(format!(
@@ -331,10 +1647,187 @@ fn build_azure_devops_url(team: &str, repo: &str, cfg: &Config, page: &Page) ->
             "https://dev.azure.com/{}/{}/_workitems/edit/{}",
             team, repo, number
         )),
+        Page::PullRequestFile {
+            ref relative_path, ..
+        } => build_azure_devops_new_pr_url(team, repo, Some(relative_path), cfg),
+        Page::PullRequest { number } => Ok(format!(
+            "https://dev.azure.com/{}/_git/{}/pullrequest/{}",
+            team, repo, number
+        )),
+        Page::Section { section } => match azure_devops_section_url(team, repo, *section) {
+            Some(url) => Ok(url),
+            None => section_not_supported(*section, "dev.azure.com"),
+        },
+        Page::CompareUpstream { .. } => Error::err(ErrorKind::CompareUpstreamNotSupported {
+            service: "dev.azure.com".to_string(),
+        }),
+        Page::EditFile { ide: None, .. } => Error::err(ErrorKind::EditNotSupported {
+            service: "dev.azure.com".to_string(),
+        }),
+        Page::EditFile { ide: Some(ide), .. } => web_ide_not_supported(*ide, "dev.azure.com"),
+        // Content is fetched via Items API
+        //   https://docs.microsoft.com/en-us/rest/api/azure/devops/git/items/get
+        Page::RawFile {
+            ref relative_path,
+            ref hash,
+            lfs,
+        } => {
+            let mut url = format!(
+                "https://dev.azure.com/{}/{}/_apis/git/repositories/{}/items",
+                team, repo, repo
+            );
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            query.append_pair("path", &format!("/{}", to_slash(relative_path)));
+            query.append_pair("versionDescriptor.version", hash);
+            query.append_pair(
+                "versionDescriptor.versionType",
+                if is_commit_hash(hash) {
+                    "commit"
+                } else {
+                    "branch"
+                },
+            );
+            if *lfs {
+                query.append_pair("resolveLfs", "true");
+            }
+            query.append_pair("api-version", "6.0");
+            append_query(&mut url, query);
+            Ok(url)
+        }
+        // Items API can download the whole tree as zip file but no tarball is available
+        Page::Archive {
+            ref name,
+            tag,
+            format: ArchiveFormat::Zip,
+        } => {
+            let mut url = format!(
+                "https://dev.azure.com/{}/{}/_apis/git/repositories/{}/items",
+                team, repo, repo
+            );
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            query.append_pair("path", "/");
+            query.append_pair("versionDescriptor.version", name);
+            query.append_pair(
+                "versionDescriptor.versionType",
+                if *tag { "tag" } else { "commit" },
+            );
+            query.append_pair("$format", "zip");
+            query.append_pair("download", "true");
+            query.append_pair("api-version", "6.0");
+            append_query(&mut url, query);
+            Ok(url)
+        }
+        Page::Archive { format, .. } => Error::err(ErrorKind::ArchiveFormatNotSupported {
+            format: *format,
+            service: "dev.azure.com".to_string(),
+        }),
+        // Note: Path and version are given as query parameters
+        Page::History {
+            ref relative_path,
+            ref hash,
+        } => {
+            let version = if is_commit_hash(hash) {
+                format!("GC{}", hash)
+            } else {
+                format!("GB{}", hash)
+            };
+            let mut url = format!("https://dev.azure.com/{}/_git/{}/commits", team, repo);
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            if let Some(path) = relative_path {
+                query.append_pair("itemPath", &format!("/{}", to_slash(path)));
+            }
+            query.append_pair("itemVersion", &version);
+            append_query(&mut url, query);
+            build_history_url(
+                url,
+                &None,
+                [Some("user"), Some("fromDate"), Some("toDate")],
+                cfg,
+                "dev.azure.com",
+            )
+        }
+        // Note: Builds cannot be filtered by commit. Commit page shows its builds instead
+        Page::Ci {
+            ref hash,
+            ref branch,
+        } => match branch {
+            Some(branch) => Ok(url_with_query(
+                format!("https://dev.azure.com/{}/{}/_build", team, repo),
+                &[("branchFilter", &format!("refs/heads/{}", branch))],
+            )),
+            None => Ok(format!(
+                "https://dev.azure.com/{}/_git/{}/commit/{}",
+                team, repo, hash
+            )),
+        },
+        Page::CodeSearch { ref query } => {
+            let opts = &cfg.search_options;
+            if cfg.branch.is_some() {
+                return search_option_not_supported("--branch", "dev.azure.com");
+            }
+            if opts.language.is_some() {
+                return search_option_not_supported("--lang", "dev.azure.com");
+            }
+            let q = code_search_query(&format!("{} repo:{}", query, repo), &[("path", &opts.path)]);
+            Ok(url_with_query(
+                format!("https://dev.azure.com/{}/{}/_search", team, repo),
+                &[("type", "code"), ("text", &q)],
+            ))
+        }
+        // Fields of a new work item can be filled with their reference names
+        Page::NewIssue {
+            ref file,
+            ref commit,
+        } => {
+            let opts = &cfg.issue_options;
+            let body = new_issue_body(cfg, file, commit)?;
+            let mut url = format!(
+                "https://dev.azure.com/{}/{}/_workitems/create/Issue",
+                team, repo
+            );
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            if let Some(ref title) = opts.title {
+                query.append_pair("[System.Title]", title);
+            }
+            if let Some(ref body) = body {
+                query.append_pair("[System.Description]", body);
+            }
+            if !opts.labels.is_empty() {
+                query.append_pair("[System.Tags]", &opts.labels.join(";"));
+            }
+            if let Some(assignee) = opts.assignees.first() {
+                query.append_pair("[System.AssignedTo]", assignee);
+            }
+            append_query(&mut url, query);
+            Ok(url)
+        }
+        Page::IssueSearch { ref text } => Ok(url_with_query(
+            format!("https://dev.azure.com/{}/{}/_search", team, repo),
+            &[("type", "workitem"), ("text", text)],
+        )),
+        Page::Epic { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "epic",
+            service: "dev.azure.com".to_string(),
+        }),
+        Page::Milestone { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "milestone",
+            service: "dev.azure.com".to_string(),
+        }),
+        Page::Snippet { .. } => Error::err(ErrorKind::ReferenceNotSupported {
+            kind: "snippet",
+            service: "dev.azure.com".to_string(),
+        }),
+        Page::Qualified { .. } | Page::TrackerIssue { .. } | Page::Package { .. } => {
+            unreachable!("Page is resolved by build_page_url: {:?}", page)
+        }
         _ => Error::err(ErrorKind::AzureDevOpsNotSupported),
     }
 }
 
+fn is_commit_hash(s: &str) -> bool {
+    s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 fn is_azure_devops_host(host: &str) -> bool {
     [
         "visualstudio.com",
@@ -408,17 +1901,30 @@ pub fn slug_from_path<'a>(path: &'a str) -> Result<(&'a str, &'a str)> {
     Ok((user, repo))
 }
 
+#[derive(Debug, PartialEq)]
+pub enum Service {
+    // github.com or GitHub Enterprise
+    GitHub { api_endpoint: String },
+    GitLab,
+    Bitbucket,
+    AzureDevOps,
+}
+
+// Hosting service and slug of a repository. For Azure DevOps, user is a team name.
+#[derive(Debug, PartialEq)]
+pub struct Repository {
+    pub service: Service,
+    pub host: String, // Note: Customized port number is contained
+    pub user: String,
+    pub repo: String,
+}
+
 // Known URL formats
 //  1. https://hosting_service.com/user/repo.git
 //  2. git@hosting_service.com:user/repo.git (-> ssh://git@hosting_service.com:22/user/repo.git)
-pub fn build_page_url(page: &Page, cfg: &Config) -> Result<String> {
+pub fn resolve_repository(cfg: &Config) -> Result<Repository> {
     let repo_url = &cfg.repo_url;
-    let url = Url::parse(&repo_url).map_err(|e| {
-        Error::new(ErrorKind::BrokenUrl {
-            url: repo_url.to_string(),
-            msg: format!("{}", e),
-        })
-    })?;
+    let url = parse_repo_url(repo_url)?;
     let env = &cfg.env;
 
     let path = url.path();
@@ -429,20 +1935,23 @@ pub fn build_page_url(page: &Page, cfg: &Config) -> Result<String> {
         })
     })?;
 
-    let (user, repo_name) = if is_azure_devops_host(host) {
+    let (user, repo) = if is_azure_devops_host(host) {
         azure_devops_slug_from_path(path)?
     } else {
         slug_from_path(path)?
     };
 
-    match host {
-        "github.com" => {
-            build_github_like_url(host, user, repo_name, Some("api.github.com"), cfg, page)
-        }
-        "gitlab.com" => build_gitlab_url(host, user, repo_name, cfg, page),
-        "bitbucket.org" => build_bitbucket_url(user, repo_name, cfg, page),
+    let (service, host) = match host {
+        "github.com" => (
+            Service::GitHub {
+                api_endpoint: "api.github.com".to_string(),
+            },
+            Cow::Borrowed(host),
+        ),
+        "gitlab.com" => (Service::GitLab, Cow::Borrowed(host)),
+        "bitbucket.org" => (Service::Bitbucket, Cow::Borrowed(host)),
         "visualstudio.com" | "vs-ssh.visualstudio.com" | "dev.azure.com" | "ssh.dev.azure.com" => {
-            build_azure_devops_url(user, repo_name, cfg, page)
+            (Service::AzureDevOps, Cow::Borrowed("dev.azure.com"))
         }
         _ => {
             let is_gitlab = host.starts_with("gitlab.");
@@ -467,17 +1976,132 @@ pub fn build_page_url(page: &Page, cfg: &Config) -> Result<String> {
             };
 
             if is_gitlab {
-                build_gitlab_url(&host, user, repo_name, cfg, page)
+                (Service::GitLab, host)
             } else {
-                build_github_like_url(
-                    &host,
-                    user,
-                    repo_name,
-                    Some(format!("{}/api/v3", host)),
-                    cfg,
-                    page,
-                )
+                let api_endpoint = format!("{}/api/v3", host);
+                (Service::GitHub { api_endpoint }, host)
+            }
+        }
+    };
+
+    Ok(Repository {
+        service,
+        host: host.into_owned(),
+        user: user.to_string(),
+        repo: repo.to_string(),
+    })
+}
+
+pub fn parse_repo_url(repo_url: &str) -> Result<Url> {
+    Url::parse(repo_url).map_err(|e| {
+        Error::new(ErrorKind::BrokenUrl {
+            url: repo_url.to_string(),
+            msg: format!("{}", e),
+        })
+    })
+}
+
+// Config for other repository on the same host as the current repository
+fn config_for_other_repo(cfg: &Config, user: &str, repo: &str) -> Result<Config> {
+    let mut url = parse_repo_url(&cfg.repo_url)?;
+    let is_azure = url.host_str().map(is_azure_devops_host).unwrap_or(false);
+    if is_azure {
+        url.set_path(&format!("/{}/_git/{}", user, repo));
+    } else {
+        url.set_path(&format!("/{}/{}.git", user, repo));
+    }
+
+    let mut cfg = cfg.clone();
+    cfg.repo_url = url.into();
+    cfg.branch = None;
+    Ok(cfg)
+}
+
+pub fn build_page_url(page: &Page, cfg: &Config) -> Result<String> {
+    build_page_url_with_warnings(page, cfg, &mut vec![])
+}
+
+// Warnings about the page such as stale base of the pull request are pushed to `warnings`
+pub fn build_page_url_with_warnings(
+    page: &Page,
+    cfg: &Config,
+    warnings: &mut Vec<String>,
+) -> Result<String> {
+    build_url(page, cfg, warnings, true)
+}
+
+// When `sourcegraph` is false, the page is always opened on the hosting service
+fn build_url(
+    page: &Page,
+    cfg: &Config,
+    warnings: &mut Vec<String>,
+    sourcegraph: bool,
+) -> Result<String> {
+    match page {
+        Page::Qualified { user, repo, page } => {
+            let cfg = config_for_other_repo(cfg, user, repo)?;
+            return build_url(page, &cfg, warnings, sourcegraph);
+        }
+        Page::TrackerIssue { url, .. } => return Ok(url.clone()),
+        // Note: Website declared in the local repository is preferred to network access. It is only
+        // available when the local checkout is the repository being opened
+        Page::Open { website: true, .. } if cfg.local_checkout => {
+            if let Some(url) = website::find_local_website(cfg) {
+                return Ok(url);
             }
         }
+        // Note: Registry does not depend on the hosting service
+        Page::Package {
+            registry,
+            name,
+            docs,
+        } => {
+            return Ok(if *docs {
+                registry.docs_url(name)
+            } else {
+                registry.package_url(name)
+            })
+        }
+        _ => {}
+    }
+
+    let Repository {
+        service,
+        host,
+        user,
+        repo,
+    } = resolve_repository(cfg)?;
+
+    let sourcegraph_url = if sourcegraph {
+        sourcegraph_url_for(&host, cfg)
+    } else {
+        None
+    };
+
+    if let Page::CodeSearch { query } = page {
+        if let Some(url) = sourcegraph_url.or(cfg.env.sourcegraph_url.as_deref()) {
+            return Ok(build_sourcegraph_search_url(
+                url, &host, &user, &repo, query, cfg,
+            ));
+        }
+    }
+
+    // Note: Pages which Sourcegraph does not have are opened on the hosting service unless
+    // --sourcegraph is explicitly specified
+    if let Some(sourcegraph_url) = sourcegraph_url {
+        match build_sourcegraph_url(sourcegraph_url, &host, &user, &repo, cfg, page) {
+            Some(url) => return Ok(url),
+            None if cfg.sourcegraph => return Error::err(ErrorKind::SourcegraphPageNotSupported),
+            None => { /* Fall through */ }
+        }
+    }
+
+    match service {
+        Service::GitHub { api_endpoint } => {
+            build_github_like_url(&host, &user, &repo, Some(api_endpoint), cfg, page, warnings)
+        }
+        Service::GitLab => build_gitlab_url(&host, &user, &repo, cfg, page),
+        Service::Bitbucket => build_bitbucket_url(&user, &repo, cfg, page),
+        Service::AzureDevOps => build_azure_devops_url(&user, &repo, cfg, page),
     }
 }
@@ -0,0 +1,48 @@
+use crate::archive::checksum;
+use crate::config::Config;
+use crate::page::ArchiveFormat;
+use crate::test::helper::{self, empty_config};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn config(repo: &str, cwd: PathBuf, args: Vec<&str>) -> Config {
+    Config {
+        cwd,
+        args: args.into_iter().map(String::from).collect(),
+        archive: Some(ArchiveFormat::TarGz),
+        sha256: true,
+        ..empty_config(repo)
+    }
+}
+
+#[test]
+fn checksum_of_tag_archive() {
+    let dir = helper::init_temp_repo("archive-checksum");
+    fs::write(dir.join("README.md"), "hello\n").unwrap();
+    helper::git(&dir, &["add", "README.md"]);
+    helper::commit(&dir, "initial commit");
+    helper::git(&dir, &["tag", "v1.2.3"]);
+
+    // GitHub strips 'v' prefix of the tag from the top-level directory
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(&dir)
+        .args([
+            "archive",
+            "--format=tar.gz",
+            "--prefix=repo-1.2.3/",
+            "v1.2.3",
+        ])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let expected = format!(
+        "{:x}  https://github.com/user/repo/archive/refs/tags/v1.2.3.tar.gz",
+        Sha256::digest(&out.stdout),
+    );
+
+    let c = config("https://github.com/user/repo.git", dir, vec!["v1.2.3"]);
+    assert_eq!(checksum(&c).unwrap(), expected);
+}
@@ -1,5 +1,7 @@
 use crate::argv::*;
+use crate::config::{IssueOptions, LogOptions, SearchOptions};
 use crate::error::ErrorKind;
+use crate::page::{ArchiveFormat, WebIde};
 use crate::test::helper::get_root_dir;
 use std::env;
 use std::path::Path;
@@ -28,6 +30,7 @@ fn args_with_no_option() {
             assert!(!c.stdout);
             assert!(!c.website);
             assert!(!c.blame);
+            assert!(c.local_checkout);
         }
         r => assert!(false, "Failed to parse args with no option: {:?}", r),
     };
@@ -70,6 +73,202 @@ fn multiple_options() {
     };
 }
 
+#[test]
+fn pull_request_options() {
+    match Parsed::parse_iter(&[
+        "git-brws",
+        "-r",
+        "foo/bar",
+        "--pr",
+        "--base",
+        "develop",
+        "--draft",
+        "--label",
+        "bug",
+        "--label",
+        "ui",
+        "--reviewer",
+        "bob",
+        "--assignee",
+        "alice",
+        "--template",
+        "feature.md",
+    ])
+    .unwrap()
+    {
+        Parsed::OpenPage(c) => {
+            let o = c.pr_options;
+            assert_eq!(o.base, Some("develop".to_string()));
+            assert!(o.draft);
+            assert_eq!(o.labels, vec!["bug", "ui"]);
+            assert_eq!(o.reviewers, vec!["bob"]);
+            assert_eq!(o.assignees, vec!["alice"]);
+            assert_eq!(o.template, Some("feature.md".to_string()));
+        }
+        p => assert!(false, "{:?}", p),
+    }
+}
+
+#[test]
+fn new_issue_options() {
+    match Parsed::parse_iter(&[
+        "git-brws",
+        "-r",
+        "foo/bar",
+        "--new-issue",
+        "--title",
+        "Crash",
+        "--body",
+        "Steps",
+        "--label",
+        "bug",
+        "--assignee",
+        "rhysd",
+        "--template",
+        "bug.md",
+    ])
+    .unwrap()
+    {
+        Parsed::OpenPage(c) => {
+            assert!(c.new_issue);
+            assert_eq!(
+                c.issue_options,
+                IssueOptions {
+                    title: Some("Crash".to_string()),
+                    body: Some("Steps".to_string()),
+                    labels: vec!["bug".to_string()],
+                    assignees: vec!["rhysd".to_string()],
+                    template: Some("bug.md".to_string()),
+                },
+            );
+        }
+        p => assert!(false, "{:?}", p),
+    }
+}
+
+#[test]
+fn code_search_options() {
+    match Parsed::parse_iter(&[
+        "git-brws", "-r", "foo/bar", "--search", "fn main", "--path", "src", "--lang", "rust",
+    ])
+    .unwrap()
+    {
+        Parsed::OpenPage(c) => {
+            assert_eq!(c.search, Some("fn main".to_string()));
+            assert_eq!(
+                c.search_options,
+                SearchOptions {
+                    path: Some("src".to_string()),
+                    language: Some("rust".to_string()),
+                },
+            );
+        }
+        p => assert!(false, "{:?}", p),
+    }
+}
+
+#[test]
+fn log_options() {
+    match Parsed::parse_iter(&[
+        "git-brws",
+        "-r",
+        "foo/bar",
+        "--log",
+        "--author",
+        "rhysd",
+        "--since",
+        "2020-01-01",
+    ])
+    .unwrap()
+    {
+        Parsed::OpenPage(c) => {
+            assert!(c.log);
+            assert_eq!(
+                c.log_options,
+                LogOptions {
+                    author: Some("rhysd".to_string()),
+                    since: Some("2020-01-01".to_string()),
+                    until: None,
+                },
+            );
+        }
+        p => assert!(false, "{:?}", p),
+    }
+}
+
+#[test]
+fn web_ide_option() {
+    for &(args, expected) in &[
+        (&["--edit"][..], None),
+        (&["--web-ide"][..], Some(WebIde::GitHubDev)),
+        (&["--web-ide=gitpod"][..], Some(WebIde::Gitpod)),
+    ] {
+        let mut argv = vec!["git-brws", "-r", "foo/bar"];
+        argv.extend_from_slice(args);
+        match Parsed::parse_iter(&argv).unwrap() {
+            Parsed::OpenPage(c) => assert_eq!(c.web_ide, expected, "{:?}", args),
+            p => assert!(false, "{:?}", p),
+        }
+    }
+
+    match Parsed::parse_iter(&["git-brws", "-r", "foo/bar", "--web-ide=emacs"])
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::UnknownWebIde { name } => assert_eq!(name, "emacs"),
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn archive_option() {
+    for &(args, expected) in &[
+        (&[][..], None),
+        (&["--archive"][..], Some(ArchiveFormat::TarGz)),
+        (&["--archive=zip", "--sha256"][..], Some(ArchiveFormat::Zip)),
+    ] {
+        let mut argv = vec!["git-brws", "-r", "foo/bar"];
+        argv.extend_from_slice(args);
+        match Parsed::parse_iter(&argv).unwrap() {
+            Parsed::OpenPage(c) => assert_eq!(c.archive, expected, "{:?}", args),
+            p => assert!(false, "{:?}", p),
+        }
+    }
+
+    match Parsed::parse_iter(&["git-brws", "-r", "foo/bar", "--sha256"])
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::MissingRequiredOption { option, required } => {
+            assert_eq!(*option, "--sha256");
+            assert_eq!(*required, "--archive");
+        }
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn info_requires_pull_request() {
+    match Parsed::parse_iter(&["git-brws", "-r", "foo/bar", "--info"])
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::MissingRequiredOption { option, required } => {
+            assert_eq!(*option, "--info");
+            assert_eq!(*required, "--pr");
+        }
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+
+    match Parsed::parse_iter(&["git-brws", "-r", "foo/bar", "--pr", "--info", "--json"]).unwrap() {
+        Parsed::OpenPage(c) => {
+            assert!(c.info);
+            assert!(c.json);
+        }
+        p => assert!(false, "{:?}", p),
+    }
+}
+
 #[test]
 fn fix_ssh_repo_url() {
     for (url, expected) in &[
@@ -202,11 +401,33 @@ fn specify_repo_outside_repository() {
         Parsed::OpenPage(c) => {
             assert_eq!(c.cwd, root);
             assert_eq!(&c.repo_url, "https://github.com/foo/bar.git");
+            assert!(!c.local_checkout);
         }
         p => assert!(false, "{:?}", p),
     }
 }
 
+#[test]
+fn qualified_reference_outside_repository() {
+    let root = get_root_dir();
+    let dir = root.to_str().unwrap();
+    for arg in &["rust-lang/rust#12345", "rhysd/git-brws@90601f1"] {
+        match Parsed::parse_iter(&["git-brws", "-d", dir, arg]).unwrap() {
+            Parsed::OpenPage(c) => {
+                assert!(
+                    c.repo_url.starts_with("https://github.com/"),
+                    "{}",
+                    c.repo_url
+                );
+                assert_eq!(c.remote, None);
+                assert!(!c.local_checkout);
+                assert_eq!(c.args, vec![arg.to_string()]);
+            }
+            p => assert!(false, "{:?}", p),
+        }
+    }
+}
+
 #[test]
 fn search_repo_from_github_by_name() {
     skip_if_no_token_for_search!();
@@ -232,3 +453,59 @@ fn repo_specified_but_argument_is_not_empty() {
         e => assert!(false, "Unexpected error: {}", e),
     }
 }
+
+#[test]
+fn dependency_conflicts_with_repository() {
+    for &(args, conflicting) in &[
+        (&["-r", "foo/bar"][..], "--repo"),
+        (&["-R", "origin"][..], "--remote"),
+    ] {
+        let mut argv = vec!["git-brws", "--dep", "serde"];
+        argv.extend_from_slice(args);
+        match Parsed::parse_iter(&argv).unwrap_err().kind() {
+            ErrorKind::ConflictingOptions {
+                option,
+                conflicting: c,
+            } => {
+                assert_eq!(*option, "--dep");
+                assert_eq!(c, &conflicting);
+            }
+            e => assert!(false, "Unexpected error: {}", e),
+        }
+    }
+}
+
+#[test]
+fn page_options_conflict() {
+    for &(args, expected) in &[
+        (
+            &["--section", "issues", "--search", "foo"][..],
+            ("--section", "--search"),
+        ),
+        (&["--raw", "--log"][..], ("--log", "--raw")),
+        (&["--pr", "--website"][..], ("--pr", "--website")),
+        (&["--clone-url", "--ci"][..], ("--ci", "--clone-url")),
+        (&["--archive", "--raw"][..], ("--archive", "--raw")),
+        (&["--package", "--ci"][..], ("--ci", "--package")),
+        (&["--docs", "--archive"][..], ("--docs", "--archive")),
+    ] {
+        let mut argv = vec!["git-brws", "-r", "foo/bar"];
+        argv.extend_from_slice(args);
+        match Parsed::parse_iter(&argv).unwrap_err().kind() {
+            ErrorKind::ConflictingOptions {
+                option,
+                conflicting,
+            } => assert_eq!((*option, *conflicting), expected, "{:?}", args),
+            e => assert!(false, "Unexpected error for {:?}: {}", args, e),
+        }
+    }
+
+    // Options in the same group open one kind of page
+    match Parsed::parse_iter(&["git-brws", "-r", "foo/bar", "--edit", "--web-ide"]).unwrap() {
+        Parsed::OpenPage(c) => {
+            assert!(c.edit);
+            assert_eq!(c.web_ide, Some(WebIde::GitHubDev));
+        }
+        p => assert!(false, "{:?}", p),
+    }
+}
@@ -0,0 +1,27 @@
+use crate::bitbucket_api::website_from_json;
+
+#[test]
+fn website_from_response() {
+    let json = r#"{
+        "type": "repository",
+        "full_name": "user/repo",
+        "name": "repo",
+        "is_private": false,
+        "website": "https://docs.example.com",
+        "mainbranch": {"type": "branch", "name": "main"}
+    }"#;
+    assert_eq!(
+        website_from_json(json),
+        Some("https://docs.example.com".to_string()),
+    );
+
+    for json in &[
+        r#"{"full_name": "user/repo", "website": ""}"#,
+        r#"{"full_name": "user/repo", "website": null}"#,
+        r#"{"full_name": "user/repo"}"#,
+        r#"{"type": "error", "error": {"message": "Access denied"}}"#,
+        "not json",
+    ] {
+        assert_eq!(website_from_json(json), None, "{}", json);
+    }
+}
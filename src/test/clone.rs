@@ -0,0 +1,119 @@
+use crate::clone::{clone_urls, format_clone_urls, CloneUrls};
+use crate::config::Config;
+use crate::test::helper::empty_config;
+
+fn config(repo: &str) -> Config {
+    Config {
+        clone_url: true,
+        ..empty_config(repo)
+    }
+}
+
+#[test]
+fn clone_urls_of_services() {
+    for &(repo, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            [
+                "https://github.com/user/repo.git",
+                "git@github.com:user/repo.git",
+                "ssh://git@github.com/user/repo.git",
+            ],
+        ),
+        (
+            "ssh://git@github.somewhere.com:2222/user/repo.git",
+            [
+                "https://github.somewhere.com/user/repo.git",
+                "git@github.somewhere.com:user/repo.git",
+                "ssh://git@github.somewhere.com:2222/user/repo.git",
+            ],
+        ),
+        (
+            "ssh://git@gitlab.com:22/user/repo.git",
+            [
+                "https://gitlab.com/user/repo.git",
+                "git@gitlab.com:user/repo.git",
+                "ssh://git@gitlab.com/user/repo.git",
+            ],
+        ),
+        (
+            "https://bitbucket.org/user/repo",
+            [
+                "https://bitbucket.org/user/repo.git",
+                "git@bitbucket.org:user/repo.git",
+                "ssh://git@bitbucket.org/user/repo.git",
+            ],
+        ),
+        (
+            "ssh://git@ssh.dev.azure.com:22/v3/team/repo/repo",
+            [
+                "https://dev.azure.com/team/repo/_git/repo",
+                "git@ssh.dev.azure.com:v3/team/repo/repo",
+                "ssh://git@ssh.dev.azure.com/v3/team/repo/repo",
+            ],
+        ),
+        (
+            "https://dev.azure.com/team/project/_git/repo",
+            [
+                "https://dev.azure.com/team/project/_git/repo",
+                "git@ssh.dev.azure.com:v3/team/project/repo",
+                "ssh://git@ssh.dev.azure.com/v3/team/project/repo",
+            ],
+        ),
+        (
+            "ssh://git@ssh.dev.azure.com/v3/team/project/repo",
+            [
+                "https://dev.azure.com/team/project/_git/repo",
+                "git@ssh.dev.azure.com:v3/team/project/repo",
+                "ssh://git@ssh.dev.azure.com/v3/team/project/repo",
+            ],
+        ),
+        (
+            "https://dev.azure.com/team/_git/repo",
+            [
+                "https://dev.azure.com/team/repo/_git/repo",
+                "git@ssh.dev.azure.com:v3/team/repo/repo",
+                "ssh://git@ssh.dev.azure.com/v3/team/repo/repo",
+            ],
+        ),
+    ] {
+        let [https, ssh, ssh_url] = expected;
+        assert_eq!(
+            clone_urls(&config(repo)).unwrap(),
+            CloneUrls {
+                https: https.to_string(),
+                ssh: ssh.to_string(),
+                ssh_url: ssh_url.to_string(),
+            },
+            "{}",
+            repo,
+        );
+    }
+}
+
+#[test]
+fn clone_urls_with_mapped_port() {
+    let mut c = config("https://gitlab.somewhere.com/user/repo.git");
+    c.env.gitlab_ssh_port = Some(10022);
+    let urls = clone_urls(&c).unwrap();
+    assert_eq!(
+        urls.https,
+        "https://gitlab.somewhere.com:10022/user/repo.git"
+    );
+    assert_eq!(urls.ssh, "git@gitlab.somewhere.com:user/repo.git");
+}
+
+#[test]
+fn format_clone_urls_in_text_and_json() {
+    let mut c = config("https://github.com/user/repo.git");
+    assert_eq!(
+        format_clone_urls(&c).unwrap(),
+        "HTTPS: https://github.com/user/repo.git\nSSH: git@github.com:user/repo.git\nSSH URL: ssh://git@github.com/user/repo.git",
+    );
+
+    c.json = true;
+    assert_eq!(
+        format_clone_urls(&c).unwrap(),
+        r#"{"https":"https://github.com/user/repo.git","ssh":"git@github.com:user/repo.git","ssh_url":"ssh://git@github.com/user/repo.git"}"#,
+    );
+}
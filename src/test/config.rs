@@ -40,3 +40,19 @@ fn with_global_env() {
         .ok();
     assert_eq!(env.https_proxy, https_proxy);
 }
+
+#[test]
+fn sourcegraph_hosts_env() {
+    let vars = vec![(
+        "GIT_BRWS_SOURCEGRAPH_HOSTS".to_string(),
+        "github.example.com,gitlab.example.com".to_string(),
+    )];
+    let env = EnvConfig::from_iter(vars).unwrap();
+    assert_eq!(
+        env.sourcegraph_hosts,
+        vec!["github.example.com", "gitlab.example.com"],
+    );
+
+    let env = EnvConfig::from_iter(vec![]).unwrap();
+    assert!(env.sourcegraph_hosts.is_empty());
+}
@@ -0,0 +1,144 @@
+use crate::dependency::{resolve, Dependency};
+use crate::error::ErrorKind;
+use crate::test::helper::{empty_env, git};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "git-brws-test-dependency-{}-{}",
+        name,
+        std::process::id()
+    ));
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+const CARGO_LOCK: &str = r#"
+[[package]]
+name = "foo"
+version = "0.1.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "foo"
+version = "0.2.1"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "my-app"
+version = "0.1.0"
+dependencies = [
+ "foo",
+]
+"#;
+
+#[test]
+fn resolve_cargo_dependency() {
+    let root = temp_dir("cargo");
+    let project = root.join("project");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("Cargo.lock"), CARGO_LOCK).unwrap();
+
+    let cargo_home = root.join("cargo-home");
+    let src = cargo_home
+        .join("registry")
+        .join("src")
+        .join("index.crates.io-6f17d22bba15001f")
+        .join("foo-0.2.1");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(
+        src.join("Cargo.toml"),
+        "[package]\nname = \"foo\"\nversion = \"0.2.1\"\nrepository = \"https://github.com/user/foo-rs/tree/main/foo\"\n",
+    )
+    .unwrap();
+
+    let mut env = empty_env();
+    env.cargo_home = Some(cargo_home.to_str().unwrap().to_string());
+    let expected = Dependency {
+        name: "foo".to_string(),
+        version: "0.2.1".to_string(),
+        repo_url: "https://github.com/user/foo-rs.git".to_string(),
+        revision: "v0.2.1".to_string(),
+        directory: None,
+    };
+    assert_eq!(
+        resolve("foo", &project.join("src"), &env).unwrap(),
+        expected
+    );
+
+    // Commit and path recorded by `cargo package` are preferred
+    fs::write(
+        src.join(".cargo_vcs_info.json"),
+        r#"{"git": {"sha1": "90601f1037142605a32426f9ece0c07d479b9cc5"}, "path_in_vcs": "foo"}"#,
+    )
+    .unwrap();
+    let dep = resolve("foo", &project, &env).unwrap();
+    assert_eq!(dep.revision, "90601f1037142605a32426f9ece0c07d479b9cc5");
+    assert_eq!(dep.directory, Some("foo".to_string()));
+
+    match resolve("bar", &project, &env).unwrap_err().kind() {
+        ErrorKind::DependencyNotFound { name, .. } => assert_eq!(name, "bar"),
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn lockfile_outside_repository() {
+    let root = temp_dir("outside-repo");
+    fs::write(root.join("Cargo.lock"), CARGO_LOCK).unwrap();
+    let repo = root.join("repo");
+    fs::create_dir_all(repo.join("src")).unwrap();
+    git(&repo, &["init", "-q"]);
+    let repo = repo.canonicalize().unwrap();
+
+    match resolve("foo", &repo.join("src"), &empty_env())
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::DependencyNotFound { name, .. } => assert_eq!(name, "foo"),
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn resolve_npm_dependency() {
+    let project = temp_dir("npm");
+    fs::write(
+        project.join("package-lock.json"),
+        r#"{"lockfileVersion": 3, "packages": {"": {"name": "app"}, "node_modules/@scope/pkg": {"version": "1.2.3"}}}"#,
+    )
+    .unwrap();
+    let manifest_dir = project.join("node_modules").join("@scope").join("pkg");
+    fs::create_dir_all(&manifest_dir).unwrap();
+    fs::write(
+        manifest_dir.join("package.json"),
+        r#"{"name": "@scope/pkg", "repository": {"type": "git", "url": "git+https://github.com/scope/monorepo.git", "directory": "packages/pkg"}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        resolve("@scope/pkg", &project, &empty_env()).unwrap(),
+        Dependency {
+            name: "@scope/pkg".to_string(),
+            version: "1.2.3".to_string(),
+            repo_url: "https://github.com/scope/monorepo.git".to_string(),
+            revision: "v1.2.3".to_string(),
+            directory: Some("packages/pkg".to_string()),
+        },
+    );
+
+    // Shorthand of repository field
+    fs::write(
+        manifest_dir.join("package.json"),
+        r#"{"name": "@scope/pkg", "repository": "gitlab:scope/pkg", "gitHead": "0123abc"}"#,
+    )
+    .unwrap();
+    let dep = resolve("@scope/pkg", &project, &empty_env()).unwrap();
+    assert_eq!(dep.repo_url, "https://gitlab.com/scope/pkg.git");
+    assert_eq!(dep.revision, "0123abc");
+}
@@ -0,0 +1,65 @@
+use crate::error::ErrorKind;
+use crate::gitlab_api::{merge_request_url_from_json, pages_url_from_json, Client};
+use crate::test::helper::empty_env;
+
+#[tokio::test]
+async fn pages_url_request_failure() {
+    let env = empty_env();
+    let client = Client::build("unknown.endpoint.example.com", &env).unwrap();
+    match client.pages_url("user", "repo").await.unwrap_err().kind() {
+        ErrorKind::HttpClientError(..) => { /* ok */ }
+        e => assert!(false, "unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn pages_url_from_response() {
+    let json = r#"{
+        "url": "https://docs.example.com",
+        "is_unique_domain_enabled": false,
+        "force_https": true,
+        "deployments": [
+            {
+                "created_at": "2024-01-05T12:34:56.000Z",
+                "url": "https://docs.example.com",
+                "path_prefix": "",
+                "root_directory": "public"
+            }
+        ]
+    }"#;
+    assert_eq!(
+        pages_url_from_json(json),
+        Some("https://docs.example.com".to_string()),
+    );
+
+    for json in &[
+        r#"{"url": "", "deployments": []}"#,
+        r#"{"message": "404 Not Found"}"#,
+        "not json",
+    ] {
+        assert_eq!(pages_url_from_json(json), None, "{}", json);
+    }
+}
+
+#[test]
+fn merge_request_url_from_response() {
+    let json = r#"[
+        {
+            "id": 1,
+            "iid": 12,
+            "title": "Fix crash on login",
+            "state": "opened",
+            "source_branch": "topic",
+            "target_branch": "main",
+            "web_url": "https://gitlab.com/user/repo/-/merge_requests/12"
+        }
+    ]"#;
+    assert_eq!(
+        merge_request_url_from_json(json),
+        Some("https://gitlab.com/user/repo/-/merge_requests/12".to_string()),
+    );
+
+    for json in &["[]", r#"{"message": "404 Project Not Found"}"#, "not json"] {
+        assert_eq!(merge_request_url_from_json(json), None, "{}", json);
+    }
+}
@@ -1,7 +1,10 @@
-use crate::config::EnvConfig;
+use crate::config::{
+    Config, EnvConfig, IssueOptions, LogOptions, PullRequestOptions, SearchOptions,
+};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 pub fn empty_env() -> EnvConfig {
     EnvConfig {
@@ -11,8 +14,58 @@ pub fn empty_env() -> EnvConfig {
         gitlab_ssh_port: None,
         github_token: None,
         ghe_token: None,
+        gitlab_token: None,
+        bitbucket_token: None,
+        sourcegraph_url: None,
+        sourcegraph_hosts: vec![],
         https_proxy: None,
         browse_command: None,
+        branch_issue_pattern: None,
+        commit_issue_pattern: None,
+        cargo_home: None,
+    }
+}
+
+// Config with all options disabled. Tests override only the fields they care about with struct
+// update syntax so that adding a new option does not require touching every test module
+pub fn empty_config(repo: &str) -> Config {
+    Config {
+        repo_url: repo.to_string(),
+        branch: None,
+        cwd: env::current_dir().unwrap(),
+        args: vec![],
+        stdout: false,
+        pull_request: false,
+        website: false,
+        blame: false,
+        pr_options: PullRequestOptions::default(),
+        info: false,
+        json: false,
+        issues: false,
+        new_issue: false,
+        issue_options: IssueOptions::default(),
+        search: None,
+        search_options: SearchOptions::default(),
+        section: None,
+        ci: false,
+        released_in: false,
+        log: false,
+        raw: false,
+        edit: false,
+        web_ide: None,
+        sourcegraph: false,
+        archive: None,
+        sha256: false,
+        clone_url: false,
+        package: false,
+        docs: false,
+        dependency: None,
+        fork: None,
+        compare_upstream: false,
+        log_options: LogOptions::default(),
+        remote: None,
+        local_checkout: false,
+        env: empty_env(),
     }
 }
 
@@ -61,3 +114,66 @@ pub fn get_root_dir() -> PathBuf {
     }
     root
 }
+
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr),
+    );
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+// Create a new repository in temporary directory for tests which require specific history
+pub fn init_temp_repo(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("git-brws-test-{}-{}", name, std::process::id()));
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(&dir).unwrap();
+    let dir = fs::canonicalize(dir).unwrap();
+    git(&dir, &["init", "-q"]);
+    git(&dir, &["config", "user.name", "git-brws"]);
+    git(&dir, &["config", "user.email", "git-brws@example.com"]);
+    git(&dir, &["config", "commit.gpgsign", "false"]);
+    git(&dir, &["checkout", "-q", "-b", "master"]);
+    git(
+        &dir,
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://github.com/user/repo.git",
+        ],
+    );
+    dir
+}
+
+pub fn commit(dir: &Path, msg: &str) {
+    git(dir, &["commit", "-q", "--allow-empty", "-m", msg]);
+}
+
+// Emulate pushing the branch to 'origin' remote without network
+pub fn push(dir: &Path, branch: &str) {
+    let remote_ref = format!("refs/remotes/origin/{}", branch);
+    git(dir, &["update-ref", &remote_ref, branch]);
+    git(
+        dir,
+        &["config", &format!("branch.{}.remote", branch), "origin"],
+    );
+    git(
+        dir,
+        &[
+            "config",
+            &format!("branch.{}.merge", branch),
+            &format!("refs/heads/{}", branch),
+        ],
+    );
+}
@@ -0,0 +1,81 @@
+use crate::config::Config;
+use crate::error::ErrorKind;
+use crate::info;
+use crate::test::helper::{empty_config, empty_env, https_proxy};
+
+fn config(repo: &str, branch: &str, json: bool) -> Config {
+    let mut env = empty_env();
+    env.https_proxy = https_proxy();
+    Config {
+        branch: Some(branch.to_string()),
+        pull_request: true,
+        info: true,
+        json,
+        env,
+        ..empty_config(repo)
+    }
+}
+
+#[test]
+fn pull_request_info_unsupported_services() {
+    for url in &[
+        "https://gitlab.com/foo/bar.git",
+        "https://bitbucket.org/foo/bar.git",
+        "https://dev.azure.com/team/_git/repo",
+    ] {
+        let c = config(url, "topic", false);
+        match info::pull_request(&c).unwrap_err().kind() {
+            ErrorKind::PullReqNotSupported { .. } => { /* OK */ }
+            err => assert!(false, "Unexpected error for URL {}: {}", url, err),
+        }
+    }
+}
+
+#[test]
+fn pull_request_info_plain() {
+    let mut c = config(
+        "https://github.com/rust-lang/rust.vim.git",
+        "async-contextual-keyword",
+        false,
+    );
+    c.env.github_token = skip_if_no_token_for_search!();
+    let text = info::pull_request(&c).unwrap().unwrap().text;
+    let mut lines = text.lines();
+    assert!(lines.next().unwrap().starts_with("#290 "), "{}", text);
+    assert_eq!(
+        lines.next().unwrap(),
+        "URL: https://github.com/rust-lang/rust.vim/pull/290"
+    );
+    assert!(text.contains("\nAuthor: rhysd\n"), "{}", text);
+    assert!(
+        text.contains("\nHead: async-contextual-keyword\n"),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn pull_request_info_json() {
+    let mut c = config(
+        "https://github.com/rust-lang/rust.vim.git",
+        "async-contextual-keyword",
+        true,
+    );
+    c.env.github_token = skip_if_no_token_for_search!();
+    let json = info::pull_request(&c).unwrap().unwrap().text;
+    let v: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(v["number"], 290);
+    assert_eq!(v["author"], "rhysd");
+    assert_eq!(v["head"], "async-contextual-keyword");
+}
+
+#[test]
+fn pull_request_info_not_found() {
+    let mut c = config(
+        "https://github.com/rhysd/git-brws.git",
+        "this-branch-never-existing",
+        false,
+    );
+    c.env.github_token = skip_if_no_token_for_search!();
+    assert!(info::pull_request(&c).unwrap().is_none());
+}
@@ -0,0 +1,149 @@
+use crate::config::Config;
+use crate::error::ErrorKind;
+use crate::issue_ref::parse_pages;
+use crate::page::Page;
+use crate::test::helper::{commit, empty_config, git, init_temp_repo};
+use crate::url::build_urls;
+use std::path::Path;
+
+fn config(dir: &Path, args: Vec<&str>) -> Config {
+    Config {
+        cwd: dir.to_owned(),
+        args: args.into_iter().map(String::from).collect(),
+        issues: true,
+        ..empty_config("https://github.com/user/repo.git")
+    }
+}
+
+fn issue_numbers(c: &Config) -> Vec<usize> {
+    parse_pages(c)
+        .unwrap()
+        .into_iter()
+        .filter_map(|page| match page {
+            Page::Issue { number } => Some(number),
+            p => {
+                assert!(false, "Unexpected page: {:?}", p);
+                None
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn extract_issue_refs_from_branch_and_commit() {
+    let dir = init_temp_repo("issue-refs");
+    commit(&dir, "Initial commit\n\nRefs #12");
+    git(&dir, &["checkout", "-q", "-b", "fix/1234-login-crash"]);
+    commit(&dir, "Fix crash on login\n\nFixes #1234\nRefs: #56, ABC-99");
+
+    let c = config(&dir, vec![]);
+    assert_eq!(issue_numbers(&c), vec![1234, 56]);
+    assert_eq!(
+        build_urls(&c, &mut vec![]).unwrap(),
+        vec![
+            "https://github.com/user/repo/issues/1234",
+            "https://github.com/user/repo/issues/56",
+        ],
+    );
+
+    // Specific revision
+    let c = config(&dir, vec!["HEAD~"]);
+    assert_eq!(issue_numbers(&c), vec![1234, 12]);
+
+    // Customized patterns
+    let mut c = config(&dir, vec![]);
+    c.env.branch_issue_pattern = Some(r"^\w+/(\d+)-".to_string());
+    c.env.commit_issue_pattern = Some(r"Refs: #(\d+)".to_string());
+    assert_eq!(issue_numbers(&c), vec![1234, 56]);
+
+    c.env.commit_issue_pattern = Some(r"Refs: #(\d+".to_string());
+    match parse_pages(&c).unwrap_err().kind() {
+        ErrorKind::InvalidIssuePattern { pattern, .. } => assert_eq!(pattern, r"Refs: #(\d+"),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn no_issue_ref_found() {
+    let dir = init_temp_repo("no-issue-refs");
+    commit(&dir, "Initial commit");
+    git(&dir, &["checkout", "-q", "-b", "some-topic"]);
+
+    let c = config(&dir, vec![]);
+    match parse_pages(&c).unwrap_err().kind() {
+        ErrorKind::NoIssueReference { branch, revision } => {
+            assert_eq!(branch, "some-topic");
+            assert_eq!(revision, "HEAD");
+        }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+
+    let c = config(&dir, vec!["HEAD", "HEAD"]);
+    match parse_pages(&c).unwrap_err().kind() {
+        ErrorKind::WrongNumberOfArgs { .. } => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn extract_adjacent_issue_numbers_from_branch() {
+    let dir = init_temp_repo("issue-refs-adjacent");
+    commit(&dir, "Initial commit");
+    for &(branch, expected) in &[
+        ("fix-12-34", &[12, 34][..]),
+        ("fix_12_34_crash", &[12, 34][..]),
+        ("12/34", &[12, 34][..]),
+        ("v2-fix-7", &[7][..]),
+    ] {
+        let mut c = config(&dir, vec![]);
+        c.branch = Some(branch.to_string());
+        assert_eq!(issue_numbers(&c), expected, "{}", branch);
+    }
+}
+
+#[test]
+fn extract_issue_keys_of_external_tracker() {
+    let dir = init_temp_repo("issue-refs-tracker");
+    commit(&dir, "Initial commit");
+    git(&dir, &["checkout", "-q", "-b", "fix/1234-login-crash"]);
+    commit(
+        &dir,
+        "Fix crash on login\n\nUse UTF-8 for names\nRefs: ABC-99",
+    );
+
+    // Keys are ignored unless a tracker is configured for them
+    let c = config(&dir, vec![]);
+    assert_eq!(issue_numbers(&c), vec![1234]);
+
+    git(&dir, &["config", "brws.tracker.jira.pattern", r"ABC-\d+"]);
+    git(
+        &dir,
+        &[
+            "config",
+            "brws.tracker.jira.url",
+            "https://jira.example.com/browse/{key}",
+        ],
+    );
+    assert_eq!(
+        build_urls(&c, &mut vec![]).unwrap(),
+        vec![
+            "https://github.com/user/repo/issues/1234",
+            "https://jira.example.com/browse/ABC-99",
+        ],
+    );
+
+    // Keys can be extracted with customized patterns
+    let mut c = config(&dir, vec![]);
+    c.env.branch_issue_pattern = Some(r"^\w+/(\d+)-".to_string());
+    c.env.commit_issue_pattern = Some(r"Refs: (\S+)".to_string());
+    assert_eq!(
+        parse_pages(&c).unwrap(),
+        vec![
+            Page::Issue { number: 1234 },
+            Page::TrackerIssue {
+                key: "ABC-99".to_string(),
+                url: "https://jira.example.com/browse/ABC-99".to_string(),
+            },
+        ],
+    );
+}
@@ -1,11 +1,22 @@
 #[macro_use]
 mod helper;
 
+mod archive;
 mod argv;
+mod bitbucket_api;
+mod clone;
 mod config;
+mod dependency;
 mod git;
 mod github_api;
+mod gitlab_api;
+mod info;
+mod issue_ref;
+mod package;
 mod page;
 mod pull_request;
 mod service;
+mod tracker;
+mod upstream;
 mod url;
+mod website;
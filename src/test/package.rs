@@ -0,0 +1,131 @@
+use crate::package::{find_package, toml_value, Registry};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!(
+        "git-brws-test-package-{}-{}",
+        name,
+        std::process::id()
+    ));
+    if dir.exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn find_package_in_each_manifest() {
+    for &(file, content, registry, name) in &[
+        (
+            "Cargo.toml",
+            "[package]\nname = \"git-brws\"\nversion = \"0.1.0\"\n\n[dependencies]\nname = \"dummy\"\n",
+            Registry::CratesIo,
+            "git-brws",
+        ),
+        (
+            "package.json",
+            r#"{"name": "@scope/pkg", "version": "1.0.0"}"#,
+            Registry::Npm,
+            "@scope/pkg",
+        ),
+        (
+            "pyproject.toml",
+            "[build-system]\nrequires = []\n\n[tool.poetry]\nname = 'my-lib'\n",
+            Registry::PyPI,
+            "my-lib",
+        ),
+        (
+            "go.mod",
+            "module github.com/user/repo\n\ngo 1.21\n",
+            Registry::GoModules,
+            "github.com/user/repo",
+        ),
+        (
+            "foo.gemspec",
+            "Gem::Specification.new do |spec|\n  spec.name = \"foo-bar\"\nend\n",
+            Registry::RubyGems,
+            "foo-bar",
+        ),
+    ] {
+        let dir = temp_dir(file);
+        fs::write(dir.join(file), content).unwrap();
+        assert_eq!(
+            find_package(&dir, &dir).unwrap(),
+            Some((registry, name.to_string())),
+            "{}",
+            file,
+        );
+    }
+}
+
+#[test]
+fn find_package_of_workspace_member() {
+    let root = temp_dir("workspace");
+    fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    let member = root.join("crates").join("core");
+    fs::create_dir_all(member.join("src")).unwrap();
+    fs::write(member.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+
+    assert_eq!(
+        find_package(&member.join("src"), &root).unwrap(),
+        Some((Registry::CratesIo, "core".to_string())),
+    );
+    // Virtual manifest at the root has no package
+    assert_eq!(find_package(&root, &root).unwrap(), None);
+
+    let root = temp_dir("npm-workspace");
+    fs::write(
+        root.join("package.json"),
+        r#"{"name": "root", "private": true, "workspaces": ["packages/*"]}"#,
+    )
+    .unwrap();
+    let member = root.join("packages").join("ui");
+    fs::create_dir_all(&member).unwrap();
+    fs::write(member.join("package.json"), r#"{"name": "ui"}"#).unwrap();
+
+    assert_eq!(
+        find_package(&member, &root).unwrap(),
+        Some((Registry::Npm, "ui".to_string())),
+    );
+    assert_eq!(find_package(&root, &root).unwrap(), None);
+}
+
+#[test]
+fn registry_urls() {
+    assert_eq!(
+        Registry::CratesIo.package_url("git-brws"),
+        "https://crates.io/crates/git-brws",
+    );
+    assert_eq!(
+        Registry::CratesIo.docs_url("git-brws"),
+        "https://docs.rs/git-brws",
+    );
+    assert_eq!(
+        Registry::PyPI.docs_url("My_Lib"),
+        "https://my-lib.readthedocs.io/",
+    );
+    assert_eq!(
+        Registry::RubyGems.docs_url("foo"),
+        "https://www.rubydoc.info/gems/foo",
+    );
+}
+
+#[test]
+fn toml_value_of_quoted_string() {
+    let content = "title = \"top\"\n\n[package]\nnamespace = \"ns\"\nname = \"foo\" # comment\nversion = 1\nhomepage = \"https://example.com\n";
+    assert_eq!(
+        toml_value(content, &["[package]"], "name"),
+        Some("foo".to_string()),
+    );
+    assert_eq!(toml_value(content, &[""], "title"), Some("top".to_string()),);
+    assert_eq!(toml_value(content, &["[package]"], "version"), None);
+    assert_eq!(toml_value(content, &["[package]"], "homepage"), None);
+    assert_eq!(toml_value(content, &["[project]"], "name"), None);
+}
@@ -1,22 +1,18 @@
 use crate::config::Config;
+use crate::dependency::Dependency;
 use crate::error::ErrorKind;
-use crate::page::{parse_page, DiffOp, Line, Page};
-use crate::test::helper::{empty_env, get_root_dir};
-use std::env;
+use crate::package::Registry;
+use crate::page::{parse_page, ArchiveFormat, DiffOp, Line, Page, Section, WebIde};
+use crate::test::helper::{self, empty_config, get_root_dir};
+use crate::upstream::Fork;
+use std::fs;
 use std::path::{Path, PathBuf};
 
 fn config(repo: &str, branch: Option<&str>, args: Vec<&str>) -> Config {
     Config {
-        repo_url: repo.to_string(),
         branch: branch.map(|s| s.to_string()),
-        cwd: env::current_dir().unwrap(),
         args: args.into_iter().map(String::from).collect(),
-        stdout: false,
-        pull_request: false,
-        website: false,
-        blame: false,
-        remote: None,
-        env: empty_env(),
+        ..empty_config(repo)
     }
 }
 
@@ -328,6 +324,393 @@ fn issue_number() {
     }
 }
 
+#[test]
+fn parse_new_issue() {
+    let mut c = config("https://github.com/user/repo.git", None, vec![]);
+    c.new_issue = true;
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::NewIssue {
+            file: None,
+            commit: None,
+        },
+    );
+
+    c.args = vec!["README.md#L1-L3".to_string()];
+    match parse_page(&c).unwrap() {
+        Page::NewIssue {
+            file: Some(file),
+            commit: Some(commit),
+        } => {
+            match *file {
+                Page::FileOrDir {
+                    relative_path,
+                    line,
+                    ..
+                } => {
+                    assert_eq!(relative_path, "README.md");
+                    assert_eq!(line, Some(Line::Range(1, 3)));
+                }
+                p => assert!(false, "Unexpected file page {:?}", p),
+            }
+            assert_eq!(commit.len(), 40, "{}", commit);
+        }
+        p => assert!(false, "Unexpected result {:?}", p),
+    }
+}
+
+#[test]
+fn parse_section() {
+    let mut c = config("https://github.com/user/repo.git", None, vec![]);
+    c.section = Some(Section::Releases);
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::Section {
+            section: Section::Releases,
+        },
+    );
+
+    c.args = vec!["README.md".to_string()];
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::WrongNumberOfArgs { actual, .. } => assert_eq!(*actual, 1),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+
+    assert_eq!("actions".parse::<Section>().unwrap(), Section::Ci);
+    assert_eq!(
+        "Merge-Requests".parse::<Section>().unwrap(),
+        Section::PullRequests,
+    );
+    match "unknown".parse::<Section>().unwrap_err().kind() {
+        ErrorKind::UnknownSection { name } => assert_eq!(name, "unknown"),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn parse_raw_file() {
+    let dir = helper::init_temp_repo("raw-file");
+    fs::create_dir(dir.join("img")).unwrap();
+    fs::write(dir.join("install.sh"), "echo 'install'\n").unwrap();
+    fs::write(
+        dir.join("img").join("logo.png"),
+        "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 12345\n",
+    )
+    .unwrap();
+    helper::git(&dir, &["add", "."]);
+    helper::commit(&dir, "Add files");
+    let head = helper::git(&dir, &["rev-parse", "HEAD"]);
+
+    for &(file, relative_path, lfs) in &[
+        ("install.sh", "install.sh", false),
+        ("img/logo.png", "img/logo.png", true),
+    ] {
+        let path = dir.join(file);
+        let mut c = config(
+            "https://github.com/user/repo.git",
+            None,
+            vec![path.to_str().unwrap()],
+        );
+        c.cwd = dir.clone();
+        c.raw = true;
+        assert_eq!(
+            parse_page(&c).unwrap(),
+            Page::RawFile {
+                relative_path: relative_path.to_string(),
+                hash: head.clone(),
+                lfs,
+            },
+        );
+    }
+
+    let path = dir.join("img");
+    let mut c = config(
+        "https://github.com/user/repo.git",
+        None,
+        vec![path.to_str().unwrap()],
+    );
+    c.cwd = dir.clone();
+    c.raw = true;
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::CannotOpenRawDirectory { .. } => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn parse_edit_file() {
+    let dir = helper::init_temp_repo("edit-file");
+    fs::create_dir(dir.join("src")).unwrap();
+    fs::write(dir.join("src").join("lib.rs"), "fn main() {}\n").unwrap();
+    helper::git(&dir, &["add", "."]);
+    helper::commit(&dir, "Add files");
+    helper::git(&dir, &["branch", "develop"]);
+
+    let arg = format!("{}#L1", dir.join("src").join("lib.rs").to_str().unwrap());
+    let mut c = config("https://github.com/user/repo.git", None, vec![&arg]);
+    c.cwd = dir.clone();
+    c.edit = true;
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::EditFile {
+            relative_path: Path::new("src")
+                .join("lib.rs")
+                .to_str()
+                .unwrap()
+                .to_string(),
+            branch: "master".to_string(),
+            line: Some(Line::At(1)),
+            ide: None,
+        },
+    );
+
+    c.edit = false;
+    c.web_ide = Some(WebIde::Gitpod);
+    c.args.push("develop".to_string());
+    match parse_page(&c).unwrap() {
+        Page::EditFile { branch, ide, .. } => {
+            assert_eq!(branch, "develop");
+            assert_eq!(ide, Some(WebIde::Gitpod));
+        }
+        p => assert!(false, "Unexpected result {:?}", p),
+    }
+
+    c.args = vec![dir.join("src").to_str().unwrap().to_string()];
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::CannotEditDirectory { .. } => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+
+    helper::git(&dir, &["checkout", "-q", "--detach"]);
+    c.args = vec![arg];
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::BranchRequired { .. } => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn parse_history() {
+    let mut c = config("https://github.com/user/repo.git", None, vec!["README.md"]);
+    c.log = true;
+    match parse_page(&c).unwrap() {
+        Page::History {
+            relative_path,
+            hash,
+        } => {
+            assert_eq!(relative_path, Some("README.md".to_string()));
+            assert!(!hash.is_empty());
+        }
+        p => assert!(false, "Unexpected result {:?}", p),
+    }
+
+    c.branch = Some("develop".to_string());
+    c.args = vec![];
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::History {
+            relative_path: None,
+            hash: "develop".to_string(),
+        },
+    );
+
+    c.args = vec!["not-existing-file-or-revision".to_string()];
+    assert!(parse_page(&c).is_err());
+}
+
+#[test]
+fn parse_history_of_revision() {
+    let dir = helper::init_temp_repo("history-revision");
+    helper::commit(&dir, "first");
+    helper::commit(&dir, "second");
+    helper::push(&dir, "master");
+    helper::commit(&dir, "third");
+    helper::git(&dir, &["branch", "-q", "topic", "HEAD~2"]);
+
+    let mut c = config("https://github.com/user/repo.git", None, vec![]);
+    c.cwd = dir.clone();
+    c.log = true;
+
+    // Relative revisions are resolved since services cannot find them
+    for rev in &["HEAD~1", "@{u}"] {
+        c.args = vec![rev.to_string()];
+        assert_eq!(
+            parse_page(&c).unwrap(),
+            Page::History {
+                relative_path: None,
+                hash: helper::git(&dir, &["rev-parse", "HEAD~1"]),
+            },
+            "{}",
+            rev,
+        );
+    }
+
+    // Abbreviated hash is resolved into full hash
+    let hash = helper::git(&dir, &["rev-parse", "HEAD~2"]);
+    c.args = vec![hash[..7].to_string()];
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::History {
+            relative_path: None,
+            hash: hash.clone(),
+        },
+    );
+
+    // History of the branch is opened with branch name
+    c.args = vec!["topic".to_string()];
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::History {
+            relative_path: None,
+            hash: "topic".to_string(),
+        },
+    );
+}
+
+#[test]
+fn parse_ci() {
+    let mut c = config("https://github.com/user/repo.git", None, vec!["HEAD~1"]);
+    c.ci = true;
+    match parse_page(&c).unwrap() {
+        Page::Ci { hash, branch } => {
+            assert_eq!(hash.len(), 40, "{}", hash);
+            assert_eq!(branch, None);
+        }
+        p => assert!(false, "Unexpected result {:?}", p),
+    }
+
+    c.args.push("HEAD".to_string());
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::WrongNumberOfArgs { actual, .. } => assert_eq!(*actual, 2),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn parse_code_search() {
+    let mut c = config("https://github.com/user/repo.git", None, vec!["README.md"]);
+    c.search = Some("fn main".to_string());
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::CodeSearch {
+            query: "fn main".to_string(),
+        },
+    );
+}
+
+#[test]
+fn issue_search_text() {
+    let c = config(
+        "https://github.com/user/repo.git",
+        None,
+        vec!["#login crash"],
+    );
+    match parse_page(&c).unwrap() {
+        Page::IssueSearch { text } => assert_eq!(text, "login crash"),
+        p => assert!(false, "Unexpected result {:?}", p),
+    }
+
+    let c = config("https://github.com/user/repo.git", None, vec!["# "]);
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::PageParseError { .. } => { /* OK */ }
+        e => assert!(false, "Unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn gitlab_references() {
+    for &(arg, ref expected) in &[
+        ("!456", Page::PullRequest { number: 456 }),
+        ("&12", Page::Epic { number: 12 }),
+        ("$89", Page::Snippet { number: 89 }),
+        (
+            "%v2.0",
+            Page::Milestone {
+                name: "v2.0".to_string(),
+            },
+        ),
+        (
+            "%\"Next release\"",
+            Page::Milestone {
+                name: "Next release".to_string(),
+            },
+        ),
+    ] {
+        let c = config("https://gitlab.com/user/repo.git", None, vec![arg]);
+        assert_eq!(&parse_page(&c).unwrap(), expected, "input: {}", arg);
+    }
+
+    for arg in &["!foo", "&", "%"] {
+        let c = config("https://gitlab.com/user/repo.git", None, vec![arg]);
+        match parse_page(&c).unwrap_err().kind() {
+            ErrorKind::PageParseError { attempts, .. } => assert!(
+                attempts.iter().any(|(_, err)| match err.kind() {
+                    ErrorKind::InvalidReferenceFormat { input } => input == arg,
+                    _ => false,
+                }),
+                "{:?} for {}",
+                attempts,
+                arg,
+            ),
+            e => assert!(false, "Unexpected error {:?} for {}", e, arg),
+        }
+    }
+}
+
+#[test]
+fn qualified_references() {
+    let c = config("https://github.com/user/repo.git", None, vec!["GH-12"]);
+    assert_eq!(parse_page(&c).unwrap(), Page::Issue { number: 12 });
+
+    for &(arg, ref expected) in &[
+        (
+            "rust-lang/rust#12345",
+            Page::Qualified {
+                user: "rust-lang".to_string(),
+                repo: "rust".to_string(),
+                page: Box::new(Page::Issue { number: 12345 }),
+            },
+        ),
+        (
+            "owner/repo.js@a1b2c3d",
+            Page::Qualified {
+                user: "owner".to_string(),
+                repo: "repo.js".to_string(),
+                page: Box::new(Page::Commit {
+                    hash: "a1b2c3d".to_string(),
+                }),
+            },
+        ),
+    ] {
+        let c = config("https://github.com/user/repo.git", None, vec![arg]);
+        assert_eq!(&parse_page(&c).unwrap(), expected, "input: {}", arg);
+    }
+
+    // Existing file path is preferred
+    let c = config(
+        "https://github.com/user/repo.git",
+        None,
+        vec!["src/main.rs#1"],
+    );
+    match parse_page(&c).unwrap() {
+        Page::FileOrDir { relative_path, .. } => {
+            assert_eq!(
+                relative_path,
+                Path::new("src").join("main.rs").to_str().unwrap()
+            )
+        }
+        p => assert!(false, "Unexpected result: {:?}", p),
+    }
+
+    for arg in &["owner/repo@not-a-hash", "owner/repo#foo", "a/b/c#12"] {
+        let c = config("https://github.com/user/repo.git", None, vec![arg]);
+        match parse_page(&c).unwrap_err().kind() {
+            ErrorKind::PageParseError { .. } => { /* OK */ }
+            e => assert!(false, "Unexpected error {:?} for {}", e, arg),
+        }
+    }
+}
+
 #[test]
 fn line_cannot_be_set_to_dir() {
     for arg in &["src#123", "src#12-23"] {
@@ -399,6 +782,32 @@ fn setting_pull_request_returns_open_always() {
     }
 }
 
+#[test]
+fn parse_file_in_pull_request() {
+    let mut c = config(
+        "https://github.com/user/repo.git",
+        None,
+        vec!["README.md#L3"],
+    );
+    c.pull_request = true;
+    match parse_page(&c).unwrap() {
+        Page::PullRequestFile {
+            relative_path,
+            line,
+        } => {
+            assert_eq!(relative_path, "README.md");
+            assert_eq!(line, Some(Line::At(3)));
+        }
+        page => assert!(false, "Unexpected parse result: {:?}", page),
+    }
+
+    c.args = vec!["src".to_string()];
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::DirInPullRequest(_) => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
 #[test]
 fn parse_tag_ref() {
     let c = config(
@@ -415,6 +824,173 @@ fn parse_tag_ref() {
     }
 }
 
+#[test]
+fn parse_released_in() {
+    let dir = helper::init_temp_repo("released-in");
+    helper::commit(&dir, "initial commit");
+    helper::git(&dir, &["tag", "v0.1.0"]);
+    helper::commit(&dir, "Fix crash");
+    let fix = helper::git(&dir, &["rev-parse", "HEAD"]);
+    helper::commit(&dir, "Add feature");
+    helper::git(&dir, &["tag", "v0.2.0"]);
+    helper::commit(&dir, "Refactor");
+    helper::git(&dir, &["tag", "v0.3.0"]);
+    helper::commit(&dir, "Not released yet");
+    let tagged = helper::git(&dir, &["rev-parse", "v0.2.0"]);
+
+    let mut c = config("https://github.com/user/repo.git", None, vec![&fix]);
+    c.cwd = dir;
+    c.released_in = true;
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::Tag {
+            tagname: "v0.2.0".to_string(),
+            commit: tagged,
+        },
+    );
+
+    c.args = vec![];
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::NoTagContaining { revision } => assert_eq!(revision, "HEAD"),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn parse_compare_upstream() {
+    let mut c = config("https://github.com/orig/repo.git", Some("topic"), vec![]);
+    c.compare_upstream = true;
+    c.fork = Some(Fork {
+        user: "me".to_string(),
+        repo: "fork".to_string(),
+        upstream_default_branch: Some("main".to_string()),
+    });
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::CompareUpstream {
+            base: Some("main".to_string()),
+            user: "me".to_string(),
+            repo: "fork".to_string(),
+            branch: "topic".to_string(),
+        },
+    );
+
+    c.args = vec!["fix".to_string()];
+    match parse_page(&c).unwrap() {
+        Page::CompareUpstream { branch, .. } => assert_eq!(branch, "fix"),
+        p => assert!(false, "Unexpected page: {:?}", p),
+    }
+}
+
+#[test]
+fn parse_dependency_path() {
+    let mut c = config(
+        "https://github.com/scope/monorepo.git",
+        Some("v1.2.3"),
+        vec!["./src/index.js#L3"],
+    );
+    c.dependency = Some(Dependency {
+        name: "@scope/pkg".to_string(),
+        version: "1.2.3".to_string(),
+        repo_url: "https://github.com/scope/monorepo.git".to_string(),
+        revision: "v1.2.3".to_string(),
+        directory: Some("packages/pkg".to_string()),
+    });
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::FileOrDir {
+            relative_path: "packages/pkg/src/index.js".to_string(),
+            hash: "v1.2.3".to_string(),
+            line: Some(Line::At(3)),
+            blame: false,
+        },
+    );
+
+    c.args = vec![];
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::FileOrDir {
+            relative_path: "packages/pkg".to_string(),
+            hash: "v1.2.3".to_string(),
+            line: None,
+            blame: false,
+        },
+    );
+}
+
+#[test]
+fn parse_package() {
+    let dir = helper::init_temp_repo("package");
+    fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = [\"cli\"]\n").unwrap();
+    fs::create_dir_all(dir.join("cli").join("src")).unwrap();
+    fs::write(
+        dir.join("cli").join("Cargo.toml"),
+        "[package]\nname = \"foo-cli\"\n",
+    )
+    .unwrap();
+    let main = dir.join("cli").join("src").join("main.rs");
+    fs::write(&main, "fn main() {}\n").unwrap();
+
+    let mut c = config(
+        "https://github.com/user/repo.git",
+        None,
+        vec![main.to_str().unwrap()],
+    );
+    c.cwd = dir.clone();
+    c.docs = true;
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::Package {
+            registry: Registry::CratesIo,
+            name: "foo-cli".to_string(),
+            docs: true,
+        },
+    );
+
+    c.args = vec![];
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::NoPackageManifest { dir: d } => assert_eq!(d, &dir),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn parse_archive() {
+    let dir = helper::init_temp_repo("archive");
+    helper::commit(&dir, "initial commit");
+    helper::git(&dir, &["tag", "v0.1.0"]);
+    helper::commit(&dir, "Not released yet");
+    let head = helper::git(&dir, &["rev-parse", "HEAD"]);
+
+    let mut c = config("https://github.com/user/repo.git", None, vec!["v0.1.0"]);
+    c.cwd = dir;
+    c.archive = Some(ArchiveFormat::Zip);
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::Archive {
+            name: "v0.1.0".to_string(),
+            tag: true,
+            format: ArchiveFormat::Zip,
+        },
+    );
+
+    c.args = vec![];
+    assert_eq!(
+        parse_page(&c).unwrap(),
+        Page::Archive {
+            name: head,
+            tag: false,
+            format: ArchiveFormat::Zip,
+        },
+    );
+
+    c.args = vec!["v0.1.0".to_string(), "HEAD".to_string()];
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::WrongNumberOfArgs { kind, .. } => assert_eq!(kind, "archive"),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
 #[test]
 fn parse_blame_without_file_path() {
     for args in &[vec![], vec!["0.10.0"]] {
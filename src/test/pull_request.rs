@@ -1,6 +1,12 @@
 use crate::config::{Config, EnvConfig};
-use crate::pull_request::{find_page, Page};
-use crate::test::helper;
+use crate::github_api::{
+    CheckRun, CombinedStatus, PullRequest, PullRequestRef, Review, Status, User,
+};
+use crate::pull_request::{
+    base_branch, check_status, detect_stacked_base, failed_run_url, find_page, review_decision,
+    stale_base, Description, Page, StaleBase,
+};
+use crate::test::helper::{self, empty_config};
 
 macro_rules! env {
     () => {{
@@ -13,16 +19,9 @@ macro_rules! env {
 
 fn config(branch: Option<&str>, env: EnvConfig) -> Config {
     Config {
-        repo_url: "dummy url not used".to_string(),
         branch: branch.map(|s| s.to_string()),
-        cwd: std::env::current_dir().unwrap(),
-        args: vec![],        // Unused
-        stdout: false,       // Unused
-        pull_request: false, // Unused
-        website: false,      // Unused
-        blame: false,        // Unused
-        remote: None,        // Unused
         env,
+        ..empty_config("dummy url not used")
     }
 }
 
@@ -36,6 +35,7 @@ async fn test_find_pr_within_orig_repo() {
         page,
         Page::Existing {
             url: "https://github.com/rhysd/vim.wasm/pull/10".to_string(),
+            stale_base: None,
         },
     );
 }
@@ -50,6 +50,7 @@ async fn test_find_pr_from_fork_repo_url() {
         page,
         Page::Existing {
             url: "https://github.com/rust-lang/rust.vim/pull/290".to_string(),
+            stale_base: None,
         },
     );
 }
@@ -64,6 +65,7 @@ async fn test_find_pr_from_original_repo_url() {
         page,
         Page::Existing {
             url: "https://github.com/rust-lang/rust.vim/pull/290".to_string(),
+            stale_base: None,
         },
     );
 }
@@ -79,6 +81,7 @@ async fn test_no_pr_found_at_own_repo() {
             author,
             repo,
             branch,
+            ..
         } => {
             assert_eq!(author, "rhysd");
             assert_eq!(repo, "git-brws");
@@ -109,3 +112,338 @@ async fn test_no_pr_found_at_parent_repo() {
         p => assert!(false, "{:?}", p),
     }
 }
+
+#[test]
+fn describe_pr_from_commits() {
+    fn commits(v: &[(&str, &str)]) -> Vec<(String, String)> {
+        v.iter()
+            .map(|(s, b)| (s.to_string(), b.to_string()))
+            .collect()
+    }
+
+    assert_eq!(Description::from_commits(&[]), Description::default());
+    assert_eq!(
+        Description::from_commits(&commits(&[("Fix crash", "")])),
+        Description {
+            title: Some("Fix crash".to_string()),
+            body: None,
+        },
+    );
+    assert_eq!(
+        Description::from_commits(&commits(&[("Fix crash", "Details\n\nMore details")])),
+        Description {
+            title: Some("Fix crash".to_string()),
+            body: Some("Details\n\nMore details".to_string()),
+        },
+    );
+    assert_eq!(
+        Description::from_commits(&commits(&[("Add foo", "body"), ("Fix bar", "")])),
+        Description {
+            title: None,
+            body: Some("- Add foo\n- Fix bar".to_string()),
+        },
+    );
+}
+
+#[test]
+fn detect_base_of_stacked_branches() {
+    let dir = helper::init_temp_repo("stacked-branches");
+    helper::commit(&dir, "initial commit");
+    helper::push(&dir, "master");
+    helper::git(
+        &dir,
+        &[
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/master",
+        ],
+    );
+    helper::git(&dir, &["checkout", "-q", "-b", "feature-a"]);
+    helper::commit(&dir, "Add feature A");
+    helper::commit(&dir, "Fix feature A");
+    helper::push(&dir, "feature-a");
+    helper::git(&dir, &["checkout", "-q", "-b", "feature-b"]);
+    helper::commit(&dir, "Add feature B");
+    helper::push(&dir, "feature-b");
+    helper::git(&dir, &["checkout", "-q", "-b", "not-pushed", "master"]);
+    helper::commit(&dir, "Not pushed yet");
+    helper::git(&dir, &["checkout", "-q", "feature-b"]);
+
+    let mut cfg = config(None, helper::empty_env());
+    cfg.cwd = dir;
+    let cfg = cfg;
+
+    assert_eq!(
+        detect_stacked_base("feature-b", &cfg, &[]),
+        Some("feature-a".to_string()),
+    );
+    // feature-b is stacked on feature-a so it is not a candidate
+    assert_eq!(detect_stacked_base("feature-a", &cfg, &[]), None);
+    assert_eq!(detect_stacked_base("not-pushed", &cfg, &[]), None);
+    assert_eq!(detect_stacked_base("unknown-branch", &cfg, &[]), None);
+
+    let base = base_branch("feature-b", &cfg, &[]);
+    assert_eq!(
+        Description::from_local_commits("feature-b", base.as_deref(), &cfg),
+        Description {
+            title: Some("Add feature B".to_string()),
+            body: None,
+        },
+    );
+    assert_eq!(
+        Description::from_local_commits("feature-b", None, &cfg),
+        Description {
+            title: None,
+            body: Some("- Add feature A\n- Fix feature A\n- Add feature B".to_string()),
+        },
+    );
+
+    let mut cfg = cfg;
+    cfg.pr_options.base = Some("develop".to_string());
+    assert_eq!(
+        base_branch("feature-b", &cfg, &[]),
+        Some("develop".to_string())
+    );
+}
+
+// Open pull request whose head is the commit of the local branch
+fn open_pr(dir: &std::path::Path, owner: &str, head: &str, base: &str) -> PullRequest {
+    let mut pr = pull_request(vec![]);
+    pr.head = PullRequestRef {
+        name: head.to_string(),
+        sha: helper::git(dir, &["rev-parse", head]),
+        label: format!("{}:{}", owner, head),
+    };
+    pr.base = PullRequestRef {
+        name: base.to_string(),
+        sha: helper::git(dir, &["rev-parse", base]),
+        label: format!("user:{}", base),
+    };
+    pr
+}
+
+#[test]
+fn detect_base_from_open_pull_requests() {
+    let dir = helper::init_temp_repo("stacked-open-prs");
+    helper::commit(&dir, "initial commit");
+    helper::push(&dir, "master");
+    helper::git(
+        &dir,
+        &[
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/master",
+        ],
+    );
+    // 'feature-a' is only fetched as the head of the pull request. It does not track remote
+    helper::git(&dir, &["checkout", "-q", "-b", "feature-a"]);
+    helper::commit(&dir, "Add feature A");
+    helper::git(&dir, &["checkout", "-q", "-b", "feature-b"]);
+    helper::commit(&dir, "Add feature B");
+    helper::push(&dir, "feature-b");
+
+    let mut cfg = config(None, helper::empty_env());
+    cfg.cwd = dir.clone();
+    let cfg = cfg;
+
+    assert_eq!(detect_stacked_base("feature-b", &cfg, &[]), None);
+    let prs = vec![open_pr(&dir, "user", "feature-a", "master")];
+    assert_eq!(
+        detect_stacked_base("feature-b", &cfg, &prs),
+        Some("feature-a".to_string()),
+    );
+    // Head of pull request from fork is not a branch of the repository
+    let forked = vec![open_pr(&dir, "someone", "feature-a", "master")];
+    assert_eq!(detect_stacked_base("feature-b", &cfg, &forked), None);
+
+    // Pull request of feature-b was created before feature-a was stacked
+    assert_eq!(
+        stale_base("feature-b", "master", &prs, &cfg),
+        Some(StaleBase {
+            pr_base: "master".to_string(),
+            local_base: Some("feature-a".to_string()),
+        }),
+    );
+    assert_eq!(stale_base("feature-b", "feature-a", &prs, &cfg), None);
+    // Default branch is not a topic branch
+    assert_eq!(stale_base("feature-b", "master", &[], &cfg), None);
+    // Branch was rebased onto the default branch but the pull request was not retargeted
+    helper::git(&dir, &["checkout", "-q", "-b", "feature-c", "master"]);
+    helper::commit(&dir, "Add feature C");
+    helper::push(&dir, "feature-c");
+    assert_eq!(
+        stale_base("feature-c", "feature-a", &prs, &cfg),
+        Some(StaleBase {
+            pr_base: "feature-a".to_string(),
+            local_base: None,
+        }),
+    );
+    assert_eq!(
+        StaleBase {
+            pr_base: "feature-a".to_string(),
+            local_base: None,
+        }
+        .to_string(),
+        "Base branch of the pull request is 'feature-a', but the branch is based on the default branch locally",
+    );
+}
+
+fn user(login: &str) -> User {
+    User {
+        login: login.to_string(),
+    }
+}
+
+fn pull_request(requested_reviewers: Vec<User>) -> PullRequest {
+    let branch = |name: &str| PullRequestRef {
+        name: name.to_string(),
+        sha: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+        label: format!("user:{}", name),
+    };
+    PullRequest {
+        number: 1,
+        html_url: "https://github.com/user/repo/pull/1".to_string(),
+        title: "title".to_string(),
+        state: "open".to_string(),
+        merged: false,
+        draft: false,
+        user: user("user"),
+        base: branch("master"),
+        head: branch("topic"),
+        requested_reviewers,
+    }
+}
+
+#[test]
+fn review_decision_from_reviews() {
+    let review = |login: &str, state: &str| Review {
+        user: user(login),
+        state: state.to_string(),
+    };
+
+    let pr = pull_request(vec![]);
+    assert_eq!(review_decision(&pr, &[]), None);
+    assert_eq!(
+        review_decision(&pull_request(vec![user("alice")]), &[]),
+        Some("review_required"),
+    );
+    assert_eq!(
+        review_decision(&pr, &[review("alice", "APPROVED")]),
+        Some("approved"),
+    );
+    assert_eq!(
+        review_decision(
+            &pr,
+            &[
+                review("alice", "APPROVED"),
+                review("bob", "CHANGES_REQUESTED")
+            ],
+        ),
+        Some("changes_requested"),
+    );
+    // Only the latest review of each reviewer is considered. Comments are ignored.
+    assert_eq!(
+        review_decision(
+            &pr,
+            &[
+                review("bob", "CHANGES_REQUESTED"),
+                review("bob", "APPROVED"),
+                review("bob", "COMMENTED"),
+            ],
+        ),
+        Some("approved"),
+    );
+}
+
+#[test]
+fn check_status_from_statuses_and_check_runs() {
+    let status = |state: &str, total_count: usize| CombinedStatus {
+        state: state.to_string(),
+        total_count,
+        statuses: vec![],
+    };
+    let run = |status: &str, conclusion: Option<&str>| CheckRun {
+        status: status.to_string(),
+        conclusion: conclusion.map(|s| s.to_string()),
+        html_url: None,
+    };
+
+    // Combined status is 'pending' when no status is reported
+    assert_eq!(check_status(&status("pending", 0), &[]), None);
+    assert_eq!(check_status(&status("success", 2), &[]), Some("success"));
+    assert_eq!(
+        check_status(
+            &status("pending", 0),
+            &[
+                run("completed", Some("success")),
+                run("completed", Some("skipped"))
+            ],
+        ),
+        Some("success"),
+    );
+    assert_eq!(
+        check_status(
+            &status("success", 1),
+            &[run("in_progress", None), run("completed", Some("success"))],
+        ),
+        Some("pending"),
+    );
+    assert_eq!(
+        check_status(
+            &status("pending", 1),
+            &[run("completed", Some("timed_out"))],
+        ),
+        Some("failure"),
+    );
+    assert_eq!(check_status(&status("error", 1), &[]), Some("failure"));
+}
+
+#[test]
+fn failed_run_url_from_statuses_and_check_runs() {
+    let status = |states: &[(&str, &str)]| CombinedStatus {
+        state: "failure".to_string(),
+        total_count: states.len(),
+        statuses: states
+            .iter()
+            .map(|(state, url)| Status {
+                state: state.to_string(),
+                target_url: Some(url.to_string()),
+            })
+            .collect(),
+    };
+    let run = |conclusion: &str, url: &str| CheckRun {
+        status: "completed".to_string(),
+        conclusion: Some(conclusion.to_string()),
+        html_url: Some(url.to_string()),
+    };
+
+    assert_eq!(failed_run_url(&status(&[]), &[]), None);
+    assert_eq!(
+        failed_run_url(
+            &status(&[("success", "https://ci.example.com/1")]),
+            &[run("success", "https://github.com/u/r/runs/1")],
+        ),
+        None,
+    );
+    assert_eq!(
+        failed_run_url(
+            &status(&[
+                ("success", "https://ci.example.com/1"),
+                ("error", "https://ci.example.com/2"),
+            ]),
+            &[run("success", "https://github.com/u/r/runs/1")],
+        ),
+        Some("https://ci.example.com/2"),
+    );
+    // Check runs are preferred
+    assert_eq!(
+        failed_run_url(
+            &status(&[("failure", "https://ci.example.com/1")]),
+            &[
+                run("success", "https://github.com/u/r/runs/1"),
+                run("timed_out", "https://github.com/u/r/runs/2"),
+            ],
+        ),
+        Some("https://github.com/u/r/runs/2"),
+    );
+}
@@ -1,8 +1,8 @@
-use crate::config::{Config, EnvConfig};
+use crate::config::{Config, EnvConfig, IssueOptions, PullRequestOptions, SearchOptions};
 use crate::error::ErrorKind;
-use crate::page::{DiffOp, Line, Page};
+use crate::page::{ArchiveFormat, DiffOp, Line, Page, Section, WebIde};
 use crate::service::build_page_url;
-use crate::test::helper::{empty_env, get_root_dir, https_proxy};
+use crate::test::helper::{self, empty_config, empty_env, get_root_dir, https_proxy};
 use std::path::Path;
 
 const OPEN: Page = Page::Open {
@@ -20,16 +20,9 @@ const OPEN_PR: Page = Page::Open {
 
 fn config(repo: &str, branch: Option<&str>, env: Option<EnvConfig>) -> Config {
     Config {
-        repo_url: repo.to_string(),
         branch: branch.map(|s| s.to_string()),
-        cwd: std::env::current_dir().unwrap(),
-        args: vec![],
-        stdout: false,
-        pull_request: false,
-        website: false,
-        blame: false,
-        remote: None,
         env: env.unwrap_or_else(empty_env),
+        ..empty_config(repo)
     }
 }
 
@@ -37,19 +30,12 @@ fn config_for_pr(token: Option<String>, repo: &str, branch: Option<&str>) -> Con
     let mut env = empty_env();
     env.github_token = token;
     env.https_proxy = https_proxy();
-    let env = env;
 
     Config {
-        repo_url: repo.to_string(),
         branch: branch.map(|b| b.to_string()),
-        cwd: std::env::current_dir().unwrap(),
-        args: vec![],
-        stdout: false,
         pull_request: true,
-        website: false,
-        blame: false,
-        remote: None,
         env,
+        ..empty_config(repo)
     }
 }
 
@@ -448,231 +434,1281 @@ fn issue_number_url() {
 }
 
 #[test]
-fn unknown_github_enterprise_url() {
-    let mut env = empty_env();
-    env.ghe_url_host = Some("github-yourcompany.com".to_string());
-    let c = config(
-        "https://github-othercompany.com/foo/bar.git",
-        None,
-        Some(env),
-    );
-    match build_page_url(&OPEN, &c).unwrap_err().kind() {
-        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
-        err => assert!(false, "Unexpected error: {}", err),
-    }
-
-    let mut c = config_for_pr(None, "https://github-othercompany.com/foo/bar.git", None);
-    c.env.ghe_url_host = Some("github-yourcompany.com".to_string());
-    match build_page_url(&OPEN_PR, &c).unwrap_err().kind() {
-        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
-        err => assert!(false, "Unexpected error: {}", err),
-    }
-}
+fn new_issue_url() {
+    let file = Page::FileOrDir {
+        relative_path: "src/main.rs".to_string(),
+        hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+        line: Some(Line::Range(1, 3)),
+        blame: false,
+    };
+    let page = Page::NewIssue {
+        file: Some(Box::new(file)),
+        commit: Some("90601f1037142605a32426f9ece0c07d479b9cc5".to_string()),
+    };
+    let opts = IssueOptions {
+        title: Some("Crash".to_string()),
+        body: Some("Steps".to_string()),
+        labels: vec!["bug".to_string(), "ui".to_string()],
+        assignees: vec!["rhysd".to_string()],
+        template: None,
+    };
 
-#[test]
-fn website_github_pages() {
-    let mut env = empty_env();
-    env.github_token = skip_if_no_token!();
-    env.https_proxy = https_proxy();
-    let env = env;
-    let testcases = &[
+    for &(repo, expected) in &[
         (
-            "https://github.com/rhysd/git-brws.git", // With gh-pages
-            "https://rhysd.github.io/git-brws/",
+            "https://github.com/user/repo.git",
+            "https://github.com/user/repo/issues/new?title=Crash&body=Steps%0A%0Ahttps%3A%2F%2Fgithub.com%2Fuser%2Frepo%2Fblob%2F90601f1037142605a32426f9ece0c07d479b9cc5%2Fsrc%2Fmain.rs%23L1-L3%0A%0ACommit%3A+90601f1037142605a32426f9ece0c07d479b9cc5&labels=bug%2Cui&assignees=rhysd",
         ),
         (
-            "https://github.com/rhysd/dogfiles", // Without gh-pages, but with homepage
-            "https://rhysd.github.io",
+            "https://gitlab.com/user/repo.git",
+            "https://gitlab.com/user/repo/-/issues/new?issue%5Btitle%5D=Crash&issue%5Bdescription%5D=Steps%0A%0Ahttps%3A%2F%2Fgitlab.com%2Fuser%2Frepo%2Fblob%2F90601f1037142605a32426f9ece0c07d479b9cc5%2Fsrc%2Fmain.rs%23L1-L3%0A%0ACommit%3A+90601f1037142605a32426f9ece0c07d479b9cc5%0A%2Flabel+%7E%22bug%22%0A%2Flabel+%7E%22ui%22%0A%2Fassign+%40rhysd",
         ),
         (
-            "https://github.com/rhysd/filter-with-state", // Without homepage
-            "https://rhysd.github.io/filter-with-state",
+            "https://bitbucket.org/user/repo.git",
+            "https://bitbucket.org/user/repo/issues/new",
         ),
-    ];
-    for (url, expected) in testcases {
-        let c = config(url, None, Some(env.clone()));
-        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
-        assert_eq!(*expected, &actual);
+    ] {
+        let mut c = config(repo, None, None);
+        c.issue_options = opts.clone();
+        assert_eq!(build_page_url(&page, &c).unwrap(), expected);
     }
+
+    let page = Page::NewIssue {
+        file: None,
+        commit: None,
+    };
+    let mut c = config("https://dev.azure.com/team/repo/_git/repo", None, None);
+    c.issue_options = opts.clone();
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://dev.azure.com/team/repo/_workitems/create/Issue?%5BSystem.Title%5D=Crash&%5BSystem.Description%5D=Steps&%5BSystem.Tags%5D=bug%3Bui&%5BSystem.AssignedTo%5D=rhysd",
+    );
+
+    let c = config("https://github.com/user/repo.git", None, None);
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://github.com/user/repo/issues/new",
+    );
 }
 
 #[test]
-fn website_github_enterprise_pages() {
-    let mut env = empty_env();
-    env.ghe_url_host = Some("yourcompany-github.com".to_string());
-    env.https_proxy = https_proxy();
-    let env = env;
-
-    // TODO: Tests for the case where domain isolation is enabled are missing
-    // TODO: Tests with actual GitHub Enterprise instance
-    let testcases = &[
+fn section_url() {
+    for &(repo, section, expected) in &[
         (
-            "https://github.yourcompany.com/foo/bar.git",
-            "https://github.yourcompany.com/pages/foo/bar",
+            "https://github.com/user/repo.git",
+            Section::Ci,
+            "https://github.com/user/repo/actions",
         ),
         (
-            "https://yourcompany-github.com/foo/bar.git",
-            "https://yourcompany-github.com/pages/foo/bar",
+            "https://github.somewhere.com/user/repo.git",
+            Section::Releases,
+            "https://github.somewhere.com/user/repo/releases",
         ),
-    ];
-    for (url, expected) in testcases {
-        let c = config(url, None, Some(env.clone()));
-        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
-        assert_eq!(*expected, &actual);
-    }
-}
-
-#[test]
-fn website_gitlab_pages() {
-    let env = empty_env();
-    let testcases = &[
         (
-            "https://gitlab.com/foo/bar.git",
-            "https://foo.gitlab.io/bar",
+            "https://gitlab.com/user/repo.git",
+            Section::PullRequests,
+            "https://gitlab.com/user/repo/-/merge_requests",
         ),
         (
-            "https://gitlab.example.com/foo/bar.git",
-            "https://foo.gitlab.example.com/bar",
+            "https://gitlab.com/user/repo.git",
+            Section::Settings,
+            "https://gitlab.com/user/repo/edit",
         ),
-    ];
-    for (url, expected) in testcases {
-        let c = config(url, None, Some(env.clone()));
-        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
-        assert_eq!(*expected, &actual);
-    }
-}
-
-#[test]
-fn website_bitbucket_cloud() {
-    let mut env = empty_env();
-    env.https_proxy = https_proxy();
-    let testcases = &[
         (
-            "https://bitbucket.org/rhysd/bar.git", // Fall back to user page
-            "https://rhysd.bitbucket.io",
+            "https://bitbucket.org/user/repo.git",
+            Section::Ci,
+            "https://bitbucket.org/user/repo/pipelines",
         ),
         (
-            "https://bitbucket.org/rhysd/bb-cloud-test.git",
-            "https://rhysd.bitbucket.io/bb-cloud-test",
+            "https://dev.azure.com/team/repo/_git/repo",
+            Section::Wiki,
+            "https://dev.azure.com/team/repo/_wiki",
         ),
-    ];
-    for (url, expected) in testcases {
-        let c = config(url, None, Some(env.clone()));
-        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
-        assert_eq!(*expected, &actual);
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            Section::Branches,
+            "https://dev.azure.com/team/_git/repo/branches",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::Section { section };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
     }
-}
-
-#[test]
-fn pull_request_page_url_with_branch() {
-    let cfg = config_for_pr(
-        skip_if_no_token_for_search!(),
-        "https://github.com/rust-lang/rust.vim.git",
-        Some("async-contextual-keyword"),
-    );
-
-    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
-    assert_eq!(&url, "https://github.com/rust-lang/rust.vim/pull/290");
-}
-
-#[test]
-fn pull_request_create_page_url_at_own_repo() {
-    let cfg = config_for_pr(
-        skip_if_no_token_for_search!(),
-        "https://github.com/rhysd/git-brws.git",
-        Some("this-branch-never-existing"),
-    );
 
-    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
+    let c = config("https://gitlab.com/user/repo.git", Some("develop"), None);
+    let p = Page::Section {
+        section: Section::Insights,
+    };
     assert_eq!(
-        &url,
-        "https://github.com/rhysd/git-brws/compare/this-branch-never-existing?expand=1"
+        build_page_url(&p, &c).unwrap(),
+        "https://gitlab.com/user/repo/-/graphs/develop/charts",
     );
-}
 
-#[test]
-fn pull_request_create_page_url_at_parent_repo() {
-    let cfg = config_for_pr(
-        skip_if_no_token_for_search!(),
-        "https://github.com/rhysd/rust.vim.git",
-        Some("this-branch-never-existing"),
+    // Default branch of the remote is used when no branch is specified
+    let dir = helper::init_temp_repo("gitlab-insights");
+    helper::commit(&dir, "initial commit");
+    helper::git(&dir, &["update-ref", "refs/remotes/origin/main", "HEAD"]);
+    helper::git(
+        &dir,
+        &[
+            "symbolic-ref",
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/main",
+        ],
     );
-
-    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
+    let mut c = config("https://gitlab.com/user/repo.git", None, None);
+    c.cwd = dir;
     assert_eq!(
-        &url,
-        "https://github.com/rust-lang/rust.vim/compare/rhysd:this-branch-never-existing?expand=1"
+        build_page_url(&p, &c).unwrap(),
+        "https://gitlab.com/user/repo/-/graphs/HEAD/charts",
     );
-}
-
-#[test]
-fn pull_request_page_url_retrieving_branch_inside_repo() {
-    let cfg = config_for_pr(
-        skip_if_no_token_for_search!(),
-        "https://github.com/rhysd/git-brws.git",
-        None,
+    c.remote = Some("origin".to_string());
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://gitlab.com/user/repo/-/graphs/main/charts",
     );
 
-    // Accept both error and page since current branch may be for pull request
-    match build_page_url(&OPEN_PR, &cfg) {
-        Ok(url) => {
-            assert!(
-                url.contains("git-brws"),
-                "URL is not for git-brws repo: {}",
-                url
-            );
+    for &(repo, section) in &[
+        ("https://bitbucket.org/user/repo.git", Section::Releases),
+        ("https://dev.azure.com/team/repo/_git/repo", Section::Labels),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::Section { section };
+        match build_page_url(&p, &c).unwrap_err().kind() {
+            ErrorKind::SectionNotSupported { section: s, .. } => assert_eq!(*s, section),
+            err => assert!(false, "Unexpected error for {}: {}", repo, err),
         }
-        result => assert!(false, "Unexpected result: {:?}", result),
     }
 }
 
 #[test]
-fn pull_request_page_url_without_branch_outside_git_repo() {
-    let mut cfg = config_for_pr(None, "ssh://git@github.com:22/rhysd/git-brws.git", None);
-    cfg.cwd = get_root_dir();
-    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
-        ErrorKind::GitCommandError { .. } => { /* OK */ }
-        err => assert!(false, "Unexpected error: {} at {:?}", err, &cfg.cwd),
+fn edit_file_url() {
+    for &(repo, ide, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            None,
+            "https://github.com/user/repo/edit/fix/typo/README.md",
+        ),
+        (
+            "https://github.com/user/repo.git",
+            Some(WebIde::GitHubDev),
+            "https://github.dev/user/repo/blob/fix/typo/README.md#L3",
+        ),
+        (
+            "https://github.com/user/repo.git",
+            Some(WebIde::VsCodeDev),
+            "https://vscode.dev/github/user/repo/blob/fix/typo/README.md#L3",
+        ),
+        (
+            "https://github.com/user/repo.git",
+            Some(WebIde::Codespaces),
+            "https://codespaces.new/user/repo?ref=fix%2Ftypo",
+        ),
+        (
+            "https://github.somewhere.com/user/repo.git",
+            None,
+            "https://github.somewhere.com/user/repo/edit/fix/typo/README.md",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            None,
+            "https://gitlab.com/-/ide/project/user/repo/edit/fix/typo/-/README.md",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            Some(WebIde::Gitpod),
+            "https://gitpod.io/#https://gitlab.com/user/repo/-/blob/fix/typo/README.md",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            None,
+            "https://bitbucket.org/user/repo/src/fix/typo/README.md?mode=edit&at=fix%2Ftypo",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::EditFile {
+            relative_path: "README.md".to_string(),
+            branch: "fix/typo".to_string(),
+            line: Some(Line::At(3)),
+            ide,
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
     }
-}
 
-#[test]
-fn pull_request_unsupported_services() {
-    let urls = &[
-        "https://gitlab.com/foo/bar.git",
-        "https://gitlab.yourcompany.com/foo/bar.git",
-        "https://bitbucket.org/foo/bar.git",
-        "ssh://git@gitlab.com:22/foo/bar.git",
-        "ssh://git@gitlab.yourcompany.com:22/foo/bar.git",
-        "ssh://git@bitbucket.org:22/foo/bar.git",
-    ];
-    for url in urls {
-        let cfg = config_for_pr(None, url, None);
-        match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
-            ErrorKind::PullReqNotSupported { .. } => { /* OK */ }
-            err => assert!(false, "Unexpected error for URL {}: {}", url, err),
+    for &(repo, ide) in &[
+        (
+            "https://github.somewhere.com/user/repo.git",
+            WebIde::GitHubDev,
+        ),
+        ("https://gitlab.com/user/repo.git", WebIde::Codespaces),
+        ("https://dev.azure.com/team/repo/_git/repo", WebIde::Gitpod),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::EditFile {
+            relative_path: "README.md".to_string(),
+            branch: "master".to_string(),
+            line: None,
+            ide: Some(ide),
+        };
+        match build_page_url(&p, &c).unwrap_err().kind() {
+            ErrorKind::WebIdeNotSupported { ide: i, .. } => assert_eq!(*i, ide),
+            err => assert!(false, "Unexpected error for {}: {}", repo, err),
         }
     }
 }
 
 #[test]
-fn pull_request_github_enterprise_with_no_token() {
-    let cfg = config_for_pr(None, "https://github.yourcompany.com/foo/bar.git", None);
-    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
-        ErrorKind::GheTokenRequired => { /* OK */ }
-        err => assert!(false, "Unexpected error: {}", err),
-    }
-}
-
-#[test]
-fn tab_page_for_github_and_gitlab() {
-    let hosts = &[
-        "github.com",
-        "github.yourcompany.com",
-        "gitlab.com",
-        "gitlab.yourcompany.com",
-    ];
-    let page = Page::Tag {
+fn raw_file_url() {
+    let hash = "90601f1037142605a32426f9ece0c07d479b9cc5";
+    for &(repo, lfs, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            false,
+            "https://raw.githubusercontent.com/user/repo/90601f1037142605a32426f9ece0c07d479b9cc5/img/logo.png",
+        ),
+        (
+            "https://github.com/user/repo.git",
+            true,
+            "https://media.githubusercontent.com/media/user/repo/90601f1037142605a32426f9ece0c07d479b9cc5/img/logo.png",
+        ),
+        (
+            "https://github.somewhere.com/user/repo.git",
+            true,
+            "https://github.somewhere.com/user/repo/raw/90601f1037142605a32426f9ece0c07d479b9cc5/img/logo.png",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            true,
+            "https://gitlab.com/user/repo/-/raw/90601f1037142605a32426f9ece0c07d479b9cc5/img/logo.png",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            false,
+            "https://bitbucket.org/user/repo/raw/90601f1037142605a32426f9ece0c07d479b9cc5/img/logo.png",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            true,
+            "https://dev.azure.com/team/repo/_apis/git/repositories/repo/items?path=%2Fimg%2Flogo.png&versionDescriptor.version=90601f1037142605a32426f9ece0c07d479b9cc5&versionDescriptor.versionType=commit&resolveLfs=true&api-version=6.0",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::RawFile {
+            relative_path: "img/logo.png".to_string(),
+            hash: hash.to_string(),
+            lfs,
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn archive_url() {
+    let hash = "90601f1037142605a32426f9ece0c07d479b9cc5";
+    for &(repo, name, tag, format, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            "v1.2.3",
+            true,
+            ArchiveFormat::TarGz,
+            "https://github.com/user/repo/archive/refs/tags/v1.2.3.tar.gz",
+        ),
+        (
+            "https://github.somewhere.com/user/repo.git",
+            hash,
+            false,
+            ArchiveFormat::Zip,
+            "https://github.somewhere.com/user/repo/archive/90601f1037142605a32426f9ece0c07d479b9cc5.zip",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            "v1.2.3",
+            true,
+            ArchiveFormat::TarGz,
+            "https://gitlab.com/user/repo/-/archive/v1.2.3/repo-v1.2.3.tar.gz",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            hash,
+            false,
+            ArchiveFormat::Zip,
+            "https://bitbucket.org/user/repo/get/90601f1037142605a32426f9ece0c07d479b9cc5.zip",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            "v1.2.3",
+            true,
+            ArchiveFormat::Zip,
+            "https://dev.azure.com/team/repo/_apis/git/repositories/repo/items?path=%2F&versionDescriptor.version=v1.2.3&versionDescriptor.versionType=tag&%24format=zip&download=true&api-version=6.0",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::Archive {
+            name: name.to_string(),
+            tag,
+            format,
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+
+    let c = config("https://dev.azure.com/team/repo/_git/repo", None, None);
+    let p = Page::Archive {
+        name: "v1.2.3".to_string(),
+        tag: true,
+        format: ArchiveFormat::TarGz,
+    };
+    match build_page_url(&p, &c).unwrap_err().kind() {
+        ErrorKind::ArchiveFormatNotSupported { format, service } => {
+            assert_eq!(*format, ArchiveFormat::TarGz);
+            assert_eq!(service, "dev.azure.com");
+        }
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn compare_upstream_url() {
+    for &(repo, base, branch, expected) in &[
+        (
+            "https://github.com/orig/repo.git",
+            Some("main"),
+            "topic",
+            "https://github.com/orig/repo/compare/main...me:fork:topic",
+        ),
+        (
+            "https://github.com/orig/repo.git",
+            None,
+            "topic",
+            "https://github.com/orig/repo/compare/me:fork:topic",
+        ),
+        (
+            "https://github.com/orig/repo.git",
+            Some("release/1.0"),
+            "fix/a#1&b+c",
+            "https://github.com/orig/repo/compare/release/1.0...me:fork:fix/a%231%26b%2Bc",
+        ),
+        (
+            "https://bitbucket.org/orig/repo.git",
+            Some("main"),
+            "topic",
+            "https://bitbucket.org/me/fork/branches/compare/me/fork:topic%0Dorig/repo:main",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::CompareUpstream {
+            base: base.map(str::to_string),
+            user: "me".to_string(),
+            repo: "fork".to_string(),
+            branch: branch.to_string(),
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+
+    let c = config("https://dev.azure.com/team/repo/_git/repo", None, None);
+    let p = Page::CompareUpstream {
+        base: Some("main".to_string()),
+        user: "me".to_string(),
+        repo: "fork".to_string(),
+        branch: "topic".to_string(),
+    };
+    match build_page_url(&p, &c).unwrap_err().kind() {
+        ErrorKind::CompareUpstreamNotSupported { service } => assert_eq!(service, "dev.azure.com"),
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn history_url() {
+    let hash = "90601f1037142605a32426f9ece0c07d479b9cc5";
+    for &(repo, path, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            Some("src/page.rs"),
+            "https://github.com/user/repo/commits/90601f1037142605a32426f9ece0c07d479b9cc5/src/page.rs?author=rhysd",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            None,
+            "https://gitlab.com/user/repo/-/commits/90601f1037142605a32426f9ece0c07d479b9cc5?author=rhysd",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            Some("src/page.rs"),
+            "https://dev.azure.com/team/_git/repo/commits?itemPath=%2Fsrc%2Fpage.rs&itemVersion=GC90601f1037142605a32426f9ece0c07d479b9cc5&user=rhysd",
+        ),
+    ] {
+        let mut c = config(repo, None, None);
+        c.log_options.author = Some("rhysd".to_string());
+        let p = Page::History {
+            relative_path: path.map(|p| p.to_string()),
+            hash: hash.to_string(),
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+
+    let mut c = config("https://github.com/user/repo.git", None, None);
+    c.log_options.since = Some("2020-01-01".to_string());
+    c.log_options.until = Some("2020-01-31".to_string());
+    let p = Page::History {
+        relative_path: None,
+        hash: "master".to_string(),
+    };
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://github.com/user/repo/commits/master?since=2020-01-01&until=2020-01-31",
+    );
+
+    let c = config("https://bitbucket.org/user/repo.git", None, None);
+    let p = Page::History {
+        relative_path: Some("src/page.rs".to_string()),
+        hash: "master".to_string(),
+    };
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://bitbucket.org/user/repo/history-node/master/src/page.rs",
+    );
+    for &(rev, expected) in &[
+        (
+            "master",
+            "https://bitbucket.org/user/repo/commits/branch/master",
+        ),
+        (
+            hash,
+            "https://bitbucket.org/user/repo/commits/90601f1037142605a32426f9ece0c07d479b9cc5",
+        ),
+    ] {
+        let p = Page::History {
+            relative_path: None,
+            hash: rev.to_string(),
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+
+    for &(repo, option) in &[
+        ("https://gitlab.com/user/repo.git", "--since"),
+        ("https://bitbucket.org/user/repo.git", "--author"),
+    ] {
+        let mut c = config(repo, None, None);
+        c.log_options.author = Some("rhysd".to_string());
+        c.log_options.since = Some("2020-01-01".to_string());
+        match build_page_url(&p, &c).unwrap_err().kind() {
+            ErrorKind::LogOptionNotSupported { option: o, .. } => assert_eq!(*o, option),
+            err => assert!(false, "Unexpected error for {}: {}", repo, err),
+        }
+    }
+}
+
+#[test]
+fn ci_url() {
+    let hash = "90601f1037142605a32426f9ece0c07d479b9cc5";
+    for &(repo, branch, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            Some("fix/crash"),
+            "https://github.com/user/repo/actions?query=branch%3Afix%2Fcrash",
+        ),
+        (
+            "https://github.com/user/repo.git",
+            None,
+            "https://github.com/user/repo/commit/90601f1037142605a32426f9ece0c07d479b9cc5/checks",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            Some("develop"),
+            "https://gitlab.com/user/repo/-/pipelines?sha=90601f1037142605a32426f9ece0c07d479b9cc5",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            Some("develop"),
+            "https://bitbucket.org/user/repo/pipelines/results/branch/develop/page/1",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            None,
+            "https://bitbucket.org/user/repo/commits/90601f1037142605a32426f9ece0c07d479b9cc5",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            Some("develop"),
+            "https://dev.azure.com/team/repo/_build?branchFilter=refs%2Fheads%2Fdevelop",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        let p = Page::Ci {
+            hash: hash.to_string(),
+            branch: branch.map(|b| b.to_string()),
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn code_search_url() {
+    let p = Page::CodeSearch {
+        query: "fn main".to_string(),
+    };
+    for &(repo, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            "https://github.com/search?q=repo%3Auser%2Frepo+fn+main+path%3Asrc+language%3Arust&type=code",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            "https://bitbucket.org/search?q=repo%3Auser%2Frepo+fn+main+path%3Asrc+lang%3Arust",
+        ),
+    ] {
+        let mut c = config(repo, None, None);
+        c.search_options = SearchOptions {
+            path: Some("src".to_string()),
+            language: Some("rust".to_string()),
+        };
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+
+    let mut c = config("https://dev.azure.com/team/repo/_git/repo", None, None);
+    c.search_options.path = Some("src".to_string());
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://dev.azure.com/team/repo/_search?type=code&text=fn+main+repo%3Arepo+path%3Asrc",
+    );
+
+    // Project ID is not necessary so the URL is built without API
+    let mut c = config("https://gitlab.com/user/repo.git", Some("develop"), None);
+    c.search_options.path = Some("src".to_string());
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://gitlab.com/user/repo/-/search?search=fn+main+path%3Asrc&scope=blobs&repository_ref=develop",
+    );
+
+    for &(repo, option) in &[
+        ("https://github.com/user/repo.git", "--branch"),
+        ("https://bitbucket.org/user/repo.git", "--branch"),
+        ("https://gitlab.com/user/repo.git", "--lang"),
+    ] {
+        let mut c = config(repo, Some("develop"), None);
+        c.search_options.language = Some("rust".to_string());
+        match build_page_url(&p, &c).unwrap_err().kind() {
+            ErrorKind::SearchOptionNotSupported { option: o, .. } => assert_eq!(*o, option),
+            err => assert!(false, "Unexpected error for {}: {}", repo, err),
+        }
+    }
+}
+
+#[test]
+fn code_search_url_on_sourcegraph() {
+    let p = Page::CodeSearch {
+        query: "fn main".to_string(),
+    };
+    let mut env = empty_env();
+    env.sourcegraph_url = Some("https://sourcegraph.com/".to_string());
+    let mut c = config(
+        "https://github.com/user/repo.js.git",
+        Some("develop"),
+        Some(env),
+    );
+    c.search_options.language = Some("rust".to_string());
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://sourcegraph.com/search?q=repo%3A%5Egithub%5C.com%2Fuser%2Frepo%5C.js%24%40develop+fn+main+lang%3Arust",
+    );
+}
+
+#[test]
+fn sourcegraph_url() {
+    let mut env = empty_env();
+    env.sourcegraph_url = Some("https://sourcegraph.example.com/".to_string());
+    for (page, expected) in &[
+        (
+            Page::FileOrDir {
+                relative_path: "README.md".to_string(),
+                hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+                line: Some(Line::Range(10, 20)),
+                blame: false,
+            },
+            "https://sourcegraph.example.com/github.com/user/repo@90601f1037142605a32426f9ece0c07d479b9cc5/-/blob/README.md?L10-20",
+        ),
+        (
+            Page::FileOrDir {
+                relative_path: "src".to_string(),
+                hash: "master".to_string(),
+                line: None,
+                blame: false,
+            },
+            "https://sourcegraph.example.com/github.com/user/repo@master/-/tree/src",
+        ),
+        (
+            Page::Commit {
+                hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+            },
+            "https://sourcegraph.example.com/github.com/user/repo/-/commit/90601f1037142605a32426f9ece0c07d479b9cc5",
+        ),
+        (
+            Page::Diff {
+                lhs: "v1.0.0".to_string(),
+                rhs: "master".to_string(),
+                op: DiffOp::ThreeDots,
+            },
+            "https://sourcegraph.example.com/github.com/user/repo/-/compare/v1.0.0...master",
+        ),
+        (OPEN, "https://sourcegraph.example.com/github.com/user/repo"),
+    ] {
+        let mut c = config("https://github.com/user/repo.git", None, Some(env.clone()));
+        c.sourcegraph = true;
+        assert_eq!(build_page_url(page, &c).unwrap(), *expected);
+    }
+
+    let mut c = config("https://github.com/user/repo.git", None, None);
+    c.sourcegraph = true;
+    assert_eq!(
+        build_page_url(&OPEN, &c).unwrap(),
+        "https://sourcegraph.com/github.com/user/repo",
+    );
+    let blame = Page::FileOrDir {
+        relative_path: "README.md".to_string(),
+        hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+        line: None,
+        blame: true,
+    };
+    let new_issue = Page::NewIssue {
+        file: None,
+        commit: None,
+    };
+    for page in &[Page::Issue { number: 1 }, blame, new_issue] {
+        match build_page_url(page, &c).unwrap_err().kind() {
+            ErrorKind::SourcegraphPageNotSupported => { /* OK */ }
+            err => assert!(false, "Unexpected error for {:?}: {}", page, err),
+        }
+    }
+}
+
+#[test]
+fn sourcegraph_url_for_selected_hosts() {
+    let mut env = empty_env();
+    env.sourcegraph_url = Some("https://sourcegraph.example.com".to_string());
+    env.sourcegraph_hosts = vec!["github.example.com".to_string()];
+    let commit = Page::Commit {
+        hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+    };
+
+    let c = config(
+        "https://github.example.com/user/repo.git",
+        None,
+        Some(env.clone()),
+    );
+    assert_eq!(
+        build_page_url(&commit, &c).unwrap(),
+        "https://sourcegraph.example.com/github.example.com/user/repo/-/commit/90601f1037142605a32426f9ece0c07d479b9cc5",
+    );
+    // Pages which Sourcegraph does not have are opened on the service
+    assert_eq!(
+        build_page_url(&Page::Issue { number: 1 }, &c).unwrap(),
+        "https://github.example.com/user/repo/issues/1",
+    );
+    let file = Page::FileOrDir {
+        relative_path: "README.md".to_string(),
+        hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+        line: None,
+        blame: true,
+    };
+    assert_eq!(
+        build_page_url(&file, &c).unwrap(),
+        "https://github.example.com/user/repo/blame/90601f1037142605a32426f9ece0c07d479b9cc5/README.md",
+    );
+    // Permalink in the body of a new issue is not rewritten
+    let new_issue = Page::NewIssue {
+        file: Some(Box::new(Page::FileOrDir {
+            relative_path: "README.md".to_string(),
+            hash: "90601f1037142605a32426f9ece0c07d479b9cc5".to_string(),
+            line: None,
+            blame: false,
+        })),
+        commit: None,
+    };
+    assert_eq!(
+        build_page_url(&new_issue, &c).unwrap(),
+        "https://github.example.com/user/repo/issues/new?body=https%3A%2F%2Fgithub.example.com%2Fuser%2Frepo%2Fblob%2F90601f1037142605a32426f9ece0c07d479b9cc5%2FREADME.md",
+    );
+
+    let c = config("https://github.com/user/repo.git", None, Some(env));
+    assert_eq!(
+        build_page_url(&commit, &c).unwrap(),
+        "https://github.com/user/repo/commit/90601f1037142605a32426f9ece0c07d479b9cc5",
+    );
+}
+
+#[test]
+fn issue_search_url() {
+    let p = Page::IssueSearch {
+        text: "login crash".to_string(),
+    };
+    for &(repo, expected) in &[
+        // Token is not set for GitHub Enterprise so search API is not available
+        (
+            "https://github.somewhere.com/user/repo.git",
+            "https://github.somewhere.com/user/repo/issues?q=login+crash",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            "https://gitlab.com/user/repo/-/issues?search=login+crash",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            "https://bitbucket.org/user/repo/issues?q=login+crash",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            "https://dev.azure.com/team/repo/_search?type=workitem&text=login+crash",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn issue_search_url_with_no_hit() {
+    let mut env = empty_env();
+    env.github_token = skip_if_no_token_for_search!();
+    env.https_proxy = https_proxy();
+    let c = config("https://github.com/rhysd/git-brws.git", None, Some(env));
+    let p = Page::IssueSearch {
+        text: "this text never matches to any issue".to_string(),
+    };
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://github.com/rhysd/git-brws/issues?q=this+text+never+matches+to+any+issue",
+    );
+}
+
+#[test]
+fn gitlab_reference_urls() {
+    let milestone = Page::Milestone {
+        name: "v2.0".to_string(),
+    };
+    for (page, expected) in &[
+        (
+            Page::PullRequest { number: 456 },
+            "https://gitlab.com/user/repo/-/merge_requests/456",
+        ),
+        (
+            Page::Epic { number: 12 },
+            "https://gitlab.com/groups/user/-/epics/12",
+        ),
+        (
+            milestone,
+            "https://gitlab.com/user/repo/-/milestones?search_title=v2.0&state=all",
+        ),
+        (
+            Page::Snippet { number: 89 },
+            "https://gitlab.com/user/repo/-/snippets/89",
+        ),
+    ] {
+        let c = config("https://gitlab.com/user/repo.git", None, None);
+        assert_eq!(&build_page_url(page, &c).unwrap(), expected);
+    }
+}
+
+#[test]
+fn gitlab_reference_urls_on_other_services() {
+    let p = Page::PullRequest { number: 456 };
+    for &(repo, expected) in &[
+        (
+            "https://github.com/user/repo.git",
+            "https://github.com/user/repo/pull/456",
+        ),
+        (
+            "https://bitbucket.org/user/repo.git",
+            "https://bitbucket.org/user/repo/pull-requests/456",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            "https://dev.azure.com/team/_git/repo/pullrequest/456",
+        ),
+    ] {
+        let c = config(repo, None, None);
+        assert_eq!(build_page_url(&p, &c).unwrap(), expected);
+    }
+
+    let c = config("https://github.com/user/repo.git", None, None);
+    let p = Page::Milestone {
+        name: "Next release".to_string(),
+    };
+    assert_eq!(
+        build_page_url(&p, &c).unwrap(),
+        "https://github.com/user/repo/issues?q=milestone%3A%22Next+release%22",
+    );
+
+    for repo in &[
+        "https://github.com/user/repo.git",
+        "https://bitbucket.org/user/repo.git",
+        "https://dev.azure.com/team/repo/_git/repo",
+    ] {
+        let c = config(repo, None, None);
+        match build_page_url(&Page::Snippet { number: 89 }, &c)
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::ReferenceNotSupported { kind, .. } => assert_eq!(*kind, "snippet"),
+            err => assert!(false, "Unexpected error for {}: {}", repo, err),
+        }
+    }
+}
+
+#[test]
+fn qualified_reference_url() {
+    let issue = Page::Qualified {
+        user: "owner".to_string(),
+        repo: "other".to_string(),
+        page: Box::new(Page::Issue { number: 12 }),
+    };
+    let commit = Page::Qualified {
+        user: "owner".to_string(),
+        repo: "other".to_string(),
+        page: Box::new(Page::Commit {
+            hash: "a1b2c3d".to_string(),
+        }),
+    };
+    for &(repo, expected_issue, expected_commit) in &[
+        (
+            "https://github.com/user/repo.git",
+            "https://github.com/owner/other/issues/12",
+            "https://github.com/owner/other/commit/a1b2c3d",
+        ),
+        (
+            "ssh://git@github.somewhere.com:22/user/repo.git",
+            "https://github.somewhere.com/owner/other/issues/12",
+            "https://github.somewhere.com/owner/other/commit/a1b2c3d",
+        ),
+        (
+            "https://gitlab.com/user/repo.git",
+            "https://gitlab.com/owner/other/issues/12",
+            "https://gitlab.com/owner/other/commit/a1b2c3d",
+        ),
+        (
+            "https://dev.azure.com/team/repo/_git/repo",
+            "https://dev.azure.com/owner/other/_workitems/edit/12",
+            "https://dev.azure.com/owner/_git/other/commit/a1b2c3d",
+        ),
+    ] {
+        let c = config(repo, Some("topic"), None);
+        assert_eq!(build_page_url(&issue, &c).unwrap(), expected_issue);
+        assert_eq!(build_page_url(&commit, &c).unwrap(), expected_commit);
+    }
+}
+
+#[test]
+fn unknown_github_enterprise_url() {
+    let mut env = empty_env();
+    env.ghe_url_host = Some("github-yourcompany.com".to_string());
+    let c = config(
+        "https://github-othercompany.com/foo/bar.git",
+        None,
+        Some(env),
+    );
+    match build_page_url(&OPEN, &c).unwrap_err().kind() {
+        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+
+    let mut c = config_for_pr(None, "https://github-othercompany.com/foo/bar.git", None);
+    c.env.ghe_url_host = Some("github-yourcompany.com".to_string());
+    match build_page_url(&OPEN_PR, &c).unwrap_err().kind() {
+        ErrorKind::UnknownHostingService { .. } => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn website_github_pages() {
+    let mut env = empty_env();
+    env.github_token = skip_if_no_token!();
+    env.https_proxy = https_proxy();
+    let env = env;
+    let testcases = &[
+        (
+            "https://github.com/rhysd/git-brws.git", // With gh-pages
+            "https://rhysd.github.io/git-brws/",
+        ),
+        (
+            "https://github.com/rhysd/dogfiles", // Without gh-pages, but with homepage
+            "https://rhysd.github.io",
+        ),
+        (
+            "https://github.com/rhysd/filter-with-state", // Without homepage
+            "https://rhysd.github.io/filter-with-state",
+        ),
+    ];
+    for (url, expected) in testcases {
+        let c = config(url, None, Some(env.clone()));
+        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
+        assert_eq!(*expected, &actual);
+    }
+}
+
+#[test]
+fn website_github_enterprise_pages() {
+    let mut env = empty_env();
+    env.ghe_url_host = Some("yourcompany-github.com".to_string());
+    env.https_proxy = https_proxy();
+    let env = env;
+
+    // TODO: Tests for the case where domain isolation is enabled are missing
+    // TODO: Tests with actual GitHub Enterprise instance
+    let testcases = &[
+        (
+            "https://github.yourcompany.com/foo/bar.git",
+            "https://github.yourcompany.com/pages/foo/bar",
+        ),
+        (
+            "https://yourcompany-github.com/foo/bar.git",
+            "https://yourcompany-github.com/pages/foo/bar",
+        ),
+    ];
+    for (url, expected) in testcases {
+        let c = config(url, None, Some(env.clone()));
+        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
+        assert_eq!(*expected, &actual);
+    }
+}
+
+#[test]
+fn website_gitlab_pages() {
+    let env = empty_env();
+    let testcases = &[
+        (
+            "https://gitlab.com/foo/bar.git",
+            "https://foo.gitlab.io/bar",
+        ),
+        (
+            "https://gitlab.example.com/foo/bar.git",
+            "https://foo.gitlab.example.com/bar",
+        ),
+    ];
+    for (url, expected) in testcases {
+        let c = config(url, None, Some(env.clone()));
+        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
+        assert_eq!(*expected, &actual);
+    }
+}
+
+#[test]
+fn website_bitbucket_with_token() {
+    // Guessed URL is used when the repository API fails. Requests never reach the network
+    // through the unreachable proxy
+    let mut env = empty_env();
+    env.https_proxy = Some("http://127.0.0.1:9".to_string());
+    env.bitbucket_token = Some("invalid-token".to_string());
+    let c = config("https://bitbucket.org/rhysd/bar.git", None, Some(env));
+    assert_eq!(
+        build_page_url(&OPEN_WEBSITE, &c).unwrap(),
+        "https://rhysd.bitbucket.io",
+    );
+}
+
+#[test]
+fn website_bitbucket_cloud() {
+    let mut env = empty_env();
+    env.https_proxy = https_proxy();
+    let testcases = &[
+        (
+            "https://bitbucket.org/rhysd/bar.git", // Fall back to user page
+            "https://rhysd.bitbucket.io",
+        ),
+        (
+            "https://bitbucket.org/rhysd/bb-cloud-test.git",
+            "https://rhysd.bitbucket.io/bb-cloud-test",
+        ),
+    ];
+    for (url, expected) in testcases {
+        let c = config(url, None, Some(env.clone()));
+        let actual = build_page_url(&OPEN_WEBSITE, &c).unwrap();
+        assert_eq!(*expected, &actual);
+    }
+}
+
+#[test]
+fn pull_request_page_url_with_branch() {
+    let cfg = config_for_pr(
+        skip_if_no_token_for_search!(),
+        "https://github.com/rust-lang/rust.vim.git",
+        Some("async-contextual-keyword"),
+    );
+
+    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
+    assert_eq!(&url, "https://github.com/rust-lang/rust.vim/pull/290");
+}
+
+#[test]
+fn pull_request_create_page_url_at_own_repo() {
+    let cfg = config_for_pr(
+        skip_if_no_token_for_search!(),
+        "https://github.com/rhysd/git-brws.git",
+        Some("this-branch-never-existing"),
+    );
+
+    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
+    assert_eq!(
+        &url,
+        "https://github.com/rhysd/git-brws/compare/this-branch-never-existing?expand=1"
+    );
+}
+
+#[test]
+fn pull_request_create_page_url_at_parent_repo() {
+    let cfg = config_for_pr(
+        skip_if_no_token_for_search!(),
+        "https://github.com/rhysd/rust.vim.git",
+        Some("this-branch-never-existing"),
+    );
+
+    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
+    assert_eq!(
+        &url,
+        "https://github.com/rust-lang/rust.vim/compare/rhysd:this-branch-never-existing?expand=1"
+    );
+}
+
+#[test]
+fn pull_request_page_url_retrieving_branch_inside_repo() {
+    let cfg = config_for_pr(
+        skip_if_no_token_for_search!(),
+        "https://github.com/rhysd/git-brws.git",
+        None,
+    );
+
+    // Accept both error and page since current branch may be for pull request
+    match build_page_url(&OPEN_PR, &cfg) {
+        Ok(url) => {
+            assert!(
+                url.contains("git-brws"),
+                "URL is not for git-brws repo: {}",
+                url
+            );
+        }
+        result => assert!(false, "Unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn pull_request_page_url_without_branch_outside_git_repo() {
+    let mut cfg = config_for_pr(None, "ssh://git@github.com:22/rhysd/git-brws.git", None);
+    cfg.cwd = get_root_dir();
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::GitCommandError { .. } => { /* OK */ }
+        err => assert!(false, "Unexpected error: {} at {:?}", err, &cfg.cwd),
+    }
+}
+
+#[test]
+fn pull_request_unsupported_services() {
+    let urls = &[
+        "https://bitbucket.org/foo/bar.git",
+        "ssh://git@bitbucket.org:22/foo/bar.git",
+    ];
+    for url in urls {
+        let cfg = config_for_pr(None, url, None);
+        match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+            ErrorKind::PullReqNotSupported { .. } => { /* OK */ }
+            err => assert!(false, "Unexpected error for URL {}: {}", url, err),
+        }
+    }
+}
+
+#[test]
+fn pull_request_file_page_url() {
+    let cfg = config_for_pr(
+        skip_if_no_token_for_search!(),
+        "https://github.com/rust-lang/rust.vim.git",
+        Some("async-contextual-keyword"),
+    );
+    let page = |line: Option<Line>| Page::PullRequestFile {
+        relative_path: "syntax/rust.vim".to_string(),
+        line,
+    };
+
+    for (line, anchor) in &[
+        (None, ""),
+        (Some(Line::At(30)), "R30"),
+        (Some(Line::Range(30, 34)), "R30-R34"),
+    ] {
+        let url = build_page_url(&page(line.clone()), &cfg).unwrap();
+        assert_eq!(
+            url,
+            format!(
+                "https://github.com/rust-lang/rust.vim/pull/290/files#diff-dd5944346df108dbcf7875b7f8c605f42deb394ef51261d01c4368204894505e{}",
+                anchor,
+            ),
+        );
+    }
+}
+
+#[test]
+fn pull_request_file_create_page_url() {
+    let cfg = config_for_pr(
+        skip_if_no_token_for_search!(),
+        "https://github.com/rhysd/git-brws.git",
+        Some("this-branch-never-existing"),
+    );
+    let page = Page::PullRequestFile {
+        relative_path: "README.md".to_string(),
+        line: Some(Line::At(3)),
+    };
+
+    let url = build_page_url(&page, &cfg).unwrap();
+    assert_eq!(
+        &url,
+        "https://github.com/rhysd/git-brws/compare/this-branch-never-existing?expand=1#diff-b335630551682c19a781afebcf4d07bf978fb1f8ac04c6bf87428ed5106870f5R3"
+    );
+}
+
+#[test]
+fn pull_request_file_gitlab_and_azure_devops() {
+    let page = Page::PullRequestFile {
+        relative_path: "src/lib.rs".to_string(),
+        line: Some(Line::At(3)),
+    };
+    for &(url, expected) in &[
+        (
+            "https://gitlab.com/foo/bar.git",
+            "https://gitlab.com/foo/bar/-/merge_requests/new/diffs?merge_request%5Bsource_branch%5D=topic#b24749917179fb5e3e613ed2a703fcdcc6cdf9da",
+        ),
+        (
+            "https://dev.azure.com/foo/_git/bar",
+            "https://dev.azure.com/foo/_git/bar/pullrequestcreate?sourceRef=topic&targetRef=master&_a=files&path=%2Fsrc%2Flib.rs",
+        ),
+    ] {
+        let cfg = config_for_pr(None, url, Some("topic"));
+        assert_eq!(build_page_url(&page, &cfg).unwrap(), expected);
+    }
+}
+
+#[test]
+fn pull_request_create_page_url_with_options() {
+    let mut cfg = config_for_pr(
+        skip_if_no_token_for_search!(),
+        "https://github.com/rhysd/git-brws.git",
+        Some("this-branch-never-existing"),
+    );
+    cfg.pr_options = PullRequestOptions {
+        base: Some("master".to_string()),
+        labels: vec!["bug".to_string(), "ui".to_string()],
+        assignees: vec!["rhysd".to_string()],
+        template: Some("feature.md".to_string()),
+        ..PullRequestOptions::default()
+    };
+
+    let url = build_page_url(&OPEN_PR, &cfg).unwrap();
+    assert_eq!(
+        &url,
+        "https://github.com/rhysd/git-brws/compare/master...this-branch-never-existing?expand=1&labels=bug%2Cui&assignees=rhysd&template=feature.md"
+    );
+}
+
+#[test]
+fn pull_request_github_unsupported_options() {
+    let mut cfg = config_for_pr(None, "https://github.com/user/repo.git", Some("topic"));
+    cfg.pr_options.draft = true;
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::PullReqOptionNotSupported { option, service } => {
+            assert_eq!(*option, "--draft");
+            assert_eq!(service, "github.com");
+        }
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+
+    cfg.pr_options.draft = false;
+    cfg.pr_options.reviewers = vec!["bob".to_string()];
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::PullReqOptionNotSupported { option, .. } => assert_eq!(*option, "--reviewer"),
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn pull_request_gitlab_new_merge_request_page() {
+    for url in &[
+        "https://gitlab.com/foo/bar.git",
+        "ssh://git@gitlab.com:22/foo/bar.git",
+    ] {
+        let mut cfg = config_for_pr(None, url, Some("topic"));
+        cfg.cwd = get_root_dir();
+        assert_eq!(
+            build_page_url(&OPEN_PR, &cfg).unwrap(),
+            "https://gitlab.com/foo/bar/-/merge_requests/new?merge_request%5Bsource_branch%5D=topic",
+            "{}",
+            url,
+        );
+    }
+
+    let mut cfg = config_for_pr(
+        None,
+        "https://gitlab.yourcompany.com/foo/bar.git",
+        Some("topic"),
+    );
+    cfg.cwd = get_root_dir();
+    cfg.pr_options = PullRequestOptions {
+        base: Some("develop".to_string()),
+        draft: true,
+        labels: vec!["bug".to_string()],
+        reviewers: vec!["bob".to_string()],
+        assignees: vec!["alice".to_string()],
+        template: Some("feature".to_string()),
+    };
+    assert_eq!(
+        build_page_url(&OPEN_PR, &cfg).unwrap(),
+        "https://gitlab.yourcompany.com/foo/bar/-/merge_requests/new?merge_request%5Bsource_branch%5D=topic&merge_request%5Btarget_branch%5D=develop&merge_request%5Btitle%5D=Draft%3A+topic&merge_request%5Bdescription%5D=%2Flabel+%7E%22bug%22%0A%2Fassign+%40alice%0A%2Fassign_reviewer+%40bob&issuable_template=feature",
+    );
+}
+
+#[test]
+fn pull_request_azure_devops_base_branch() {
+    let mut cfg = config_for_pr(None, "https://dev.azure.com/team/_git/repo", Some("topic"));
+    assert_eq!(
+        build_page_url(&OPEN_PR, &cfg).unwrap(),
+        "https://dev.azure.com/team/_git/repo/pullrequestcreate?sourceRef=topic&targetRef=master",
+    );
+    cfg.pr_options.base = Some("develop".to_string());
+    assert_eq!(
+        build_page_url(&OPEN_PR, &cfg).unwrap(),
+        "https://dev.azure.com/team/_git/repo/pullrequestcreate?sourceRef=topic&targetRef=develop",
+    );
+    cfg.branch = Some("feat/a#1&b+c".to_string());
+    assert_eq!(
+        build_page_url(&OPEN_PR, &cfg).unwrap(),
+        "https://dev.azure.com/team/_git/repo/pullrequestcreate?sourceRef=feat%2Fa%231%26b%2Bc&targetRef=develop",
+    );
+}
+
+#[test]
+fn pull_request_azure_devops_unsupported_options() {
+    let mut cfg = config_for_pr(None, "https://dev.azure.com/team/_git/repo", Some("topic"));
+    cfg.pr_options.labels = vec!["bug".to_string()];
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::PullReqOptionNotSupported { option, service } => {
+            assert_eq!(*option, "--label");
+            assert_eq!(service, "dev.azure.com");
+        }
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn pull_request_github_enterprise_with_no_token() {
+    let cfg = config_for_pr(None, "https://github.yourcompany.com/foo/bar.git", None);
+    match build_page_url(&OPEN_PR, &cfg).unwrap_err().kind() {
+        ErrorKind::GheTokenRequired => { /* OK */ }
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn tab_page_for_github_and_gitlab() {
+    let hosts = &[
+        "github.com",
+        "github.yourcompany.com",
+        "gitlab.com",
+        "gitlab.yourcompany.com",
+    ];
+    let page = Page::Tag {
         tagname: "tag".to_string(),
         commit: "01234cdef".to_string(),
     };
@@ -689,6 +1725,23 @@ fn tab_page_for_github_and_gitlab() {
     }
 }
 
+#[test]
+fn release_page_for_tag() {
+    let mut env = empty_env();
+    env.github_token = skip_if_no_token!();
+    env.https_proxy = https_proxy();
+    let env = env;
+    let c = config("https://github.com/rhysd/git-brws.git", None, Some(env));
+    let page = Page::Tag {
+        tagname: "0.10.0".to_string(),
+        commit: "0b412dc7b223dd3a7fc16b6406e7b2cc866e3ed3".to_string(),
+    };
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://github.com/rhysd/git-brws/releases/tag/0.10.0",
+    );
+}
+
 #[test]
 fn tab_page_for_bitbucket() {
     let page = Page::Tag {
@@ -0,0 +1,90 @@
+use crate::config::Config;
+use crate::error::ErrorKind;
+use crate::page::{parse_page, Page};
+use crate::service::build_page_url;
+use crate::test::helper::{empty_config, git, init_temp_repo};
+use std::path::Path;
+
+fn config(dir: &Path, arg: &str) -> Config {
+    Config {
+        cwd: dir.to_owned(),
+        args: vec![arg.to_string()],
+        ..empty_config("https://github.com/user/repo.git")
+    }
+}
+
+#[test]
+fn issue_on_external_tracker() {
+    let dir = init_temp_repo("tracker");
+    git(&dir, &["config", "brws.tracker.jira.pattern", r"PLAT-\d+"]);
+    git(
+        &dir,
+        &[
+            "config",
+            "brws.tracker.jira.url",
+            "https://jira.example.com/browse/{key}",
+        ],
+    );
+    git(&dir, &["config", "brws.tracker.Linear.pattern", r"ENG-\d+"]);
+    git(
+        &dir,
+        &[
+            "config",
+            "brws.tracker.Linear.url",
+            "https://linear.app/team/issue/{key}",
+        ],
+    );
+
+    for &(arg, expected) in &[
+        ("PLAT-123", "https://jira.example.com/browse/PLAT-123"),
+        ("ENG-9", "https://linear.app/team/issue/ENG-9"),
+    ] {
+        let c = config(&dir, arg);
+        let page = parse_page(&c).unwrap();
+        assert_eq!(
+            page,
+            Page::TrackerIssue {
+                key: arg.to_string(),
+                url: expected.to_string(),
+            },
+        );
+        assert_eq!(build_page_url(&page, &c).unwrap(), expected);
+    }
+
+    // Whole argument must match the pattern
+    let c = config(&dir, "XPLAT-123");
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::PageParseError { attempts, .. } => assert!(
+            attempts.iter().any(|(_, err)| match err.kind() {
+                ErrorKind::NoTrackerMatched { input } => input == "XPLAT-123",
+                _ => false,
+            }),
+            "{:?}",
+            attempts,
+        ),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+
+    // Issue number of hosting service is still available
+    let c = config(&dir, "#12");
+    assert_eq!(parse_page(&c).unwrap(), Page::Issue { number: 12 });
+}
+
+#[test]
+fn broken_tracker_config() {
+    let dir = init_temp_repo("broken-tracker");
+    git(&dir, &["config", "brws.tracker.jira.pattern", r"PLAT-\d+"]);
+
+    let c = config(&dir, "PLAT-123");
+    match parse_page(&c).unwrap_err().kind() {
+        ErrorKind::PageParseError { attempts, .. } => assert!(
+            attempts.iter().any(|(_, err)| match err.kind() {
+                ErrorKind::TrackerConfigError { name, .. } => name == "jira",
+                _ => false,
+            }),
+            "{:?}",
+            attempts,
+        ),
+        err => assert!(false, "Unexpected error: {}", err),
+    }
+}
@@ -0,0 +1,127 @@
+use crate::argv::Parsed;
+use crate::config::Config;
+use crate::error::ErrorKind;
+use crate::git::Git;
+use crate::page::{parse_page, Page};
+use crate::service::build_page_url;
+use crate::test::helper::{self, empty_env, https_proxy};
+use crate::upstream::{resolve, Fork};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Fork cloned from gitlab.com/me/repo with 'upstream' remote whose default branch is 'main'
+fn init_fork_repo(name: &str) -> PathBuf {
+    let dir = helper::init_temp_repo(name);
+    helper::git(
+        &dir,
+        &[
+            "remote",
+            "set-url",
+            "origin",
+            "https://gitlab.com/me/repo.git",
+        ],
+    );
+    helper::git(
+        &dir,
+        &[
+            "remote",
+            "add",
+            "upstream",
+            "https://gitlab.com/orig/repo.git",
+        ],
+    );
+    fs::write(dir.join("CNAME"), "fork.example.io\n").unwrap();
+    helper::git(&dir, &["add", "CNAME"]);
+    helper::commit(&dir, "initial commit");
+    helper::push(&dir, "master");
+    helper::git(&dir, &["update-ref", "refs/remotes/upstream/main", "HEAD"]);
+    helper::git(
+        &dir,
+        &[
+            "symbolic-ref",
+            "refs/remotes/upstream/HEAD",
+            "refs/remotes/upstream/main",
+        ],
+    );
+    dir
+}
+
+fn parse(dir: &Path, args: &[&str]) -> Config {
+    let mut argv = vec!["git-brws", "-d", dir.to_str().unwrap(), "--upstream"];
+    argv.extend_from_slice(args);
+    match Parsed::parse_iter(&argv).unwrap() {
+        Parsed::OpenPage(c) => *c,
+        p => panic!("Unexpected parse result: {:?}", p),
+    }
+}
+
+#[test]
+fn resolve_upstream_via_remote() {
+    let dir = init_fork_repo("upstream-remote");
+    let git = Git::new(&dir, "git");
+    let upstream = resolve("https://gitlab.com/me/repo.git", &git, &empty_env()).unwrap();
+    assert_eq!(upstream.url, "https://gitlab.com/orig/repo.git");
+    assert_eq!(upstream.remote, Some("upstream".to_string()));
+    assert_eq!(
+        upstream.fork,
+        Fork {
+            user: "me".to_string(),
+            repo: "repo".to_string(),
+            upstream_default_branch: Some("main".to_string()),
+        },
+    );
+}
+
+#[test]
+fn upstream_not_found() {
+    let dir = helper::init_temp_repo("upstream-not-found");
+    let git = Git::new(&dir, "git");
+    let mut env = empty_env();
+    env.https_proxy = https_proxy();
+    match resolve("https://gitlab.com/me/repo.git", &git, &env)
+        .unwrap_err()
+        .kind()
+    {
+        ErrorKind::UpstreamNotFound { repo } => assert_eq!(repo, "https://gitlab.com/me/repo.git"),
+        e => assert!(false, "Unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn upstream_website_ignores_local_checkout() {
+    let dir = init_fork_repo("upstream-website");
+    let c = parse(&dir, &["--website"]);
+    assert_eq!(c.repo_url, "https://gitlab.com/orig/repo.git");
+    assert_eq!(c.remote, Some("upstream".to_string()));
+    assert!(!c.local_checkout);
+
+    // CNAME in the local checkout belongs to the fork
+    let page = Page::Open {
+        website: true,
+        pull_request: false,
+    };
+    assert_eq!(
+        build_page_url(&page, &c).unwrap(),
+        "https://orig.gitlab.io/repo"
+    );
+}
+
+#[test]
+fn upstream_file_falls_back_to_upstream_default_branch() {
+    let dir = init_fork_repo("upstream-file");
+    let head = helper::git(&dir, &["rev-parse", "HEAD"]);
+    let file = dir.join("CNAME");
+    let file = file.to_str().unwrap();
+    match parse_page(&parse(&dir, &[file])).unwrap() {
+        Page::FileOrDir { hash, .. } => assert_eq!(hash, head),
+        p => assert!(false, "Unexpected page: {:?}", p),
+    }
+
+    // Commit only in the fork is not existing in upstream
+    helper::commit(&dir, "fork only");
+    helper::push(&dir, "master");
+    match parse_page(&parse(&dir, &[file])).unwrap() {
+        Page::FileOrDir { hash, .. } => assert_eq!(hash, "main"),
+        p => assert!(false, "Unexpected page: {:?}", p),
+    }
+}
@@ -1,8 +1,7 @@
-use crate::config::{Config, EnvConfig};
+use crate::config::EnvConfig;
 use crate::error::ErrorKind;
-use crate::test::helper::empty_env;
+use crate::test::helper::{empty_config, empty_env};
 use crate::url;
-use std::env;
 
 #[cfg(not(target_os = "windows"))]
 fn executable_path(cmd: &str) -> String {
@@ -33,18 +32,7 @@ fn browse_env_config(cmd: String) -> EnvConfig {
 
 #[test]
 fn smoke() {
-    let c = Config {
-        repo_url: "ssh://git@github.com:22/rhysd/git-brws.git".to_string(),
-        branch: None,
-        cwd: env::current_dir().unwrap(),
-        args: vec![],
-        stdout: false,
-        pull_request: false,
-        website: false,
-        blame: false,
-        remote: None,
-        env: empty_env(),
-    };
+    let c = empty_config("ssh://git@github.com:22/rhysd/git-brws.git");
     match url::build_url(&c) {
         Ok(u) => assert_eq!(
             u, "https://github.com/rhysd/git-brws",
@@ -0,0 +1,112 @@
+use crate::config::Config;
+use crate::page::Page;
+use crate::service::build_page_url;
+use crate::test::helper::{self, empty_config};
+use crate::website::find_local_website;
+use std::fs;
+use std::path::PathBuf;
+
+fn config(cwd: PathBuf, remote: Option<&str>, local_checkout: bool) -> Config {
+    Config {
+        cwd,
+        website: true,
+        remote: remote.map(str::to_string),
+        local_checkout,
+        ..empty_config("https://github.com/user/repo.git")
+    }
+}
+
+#[test]
+fn local_website_precedence() {
+    let dir = helper::init_temp_repo("website");
+    helper::commit(&dir, "initial commit");
+    let c = config(dir.clone(), Some("origin"), true);
+
+    // Placeholder of Hugo quickstart is ignored
+    fs::write(dir.join("hugo.toml"), "baseURL = 'https://example.org/'\n").unwrap();
+    assert_eq!(find_local_website(&c), None);
+
+    fs::write(
+        dir.join("config.yaml"),
+        "title: Docs\nbaseURL: https://hugo.example.io/\n",
+    )
+    .unwrap();
+    assert_eq!(
+        find_local_website(&c),
+        Some("https://hugo.example.io/".to_string()),
+    );
+
+    fs::write(
+        dir.join("mkdocs.yml"),
+        "site_url: https://mkdocs.example.io/\n",
+    )
+    .unwrap();
+    assert_eq!(
+        find_local_website(&c),
+        Some("https://mkdocs.example.io/".to_string()),
+    );
+
+    fs::write(
+        dir.join("package.json"),
+        r#"{"name": "repo", "homepage": "https://npm.example.io"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        find_local_website(&c),
+        Some("https://npm.example.io".to_string()),
+    );
+
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::write(dir.join("docs").join("CNAME"), "docs.example.io\n").unwrap();
+    assert_eq!(
+        find_local_website(&c),
+        Some("https://docs.example.io".to_string()),
+    );
+
+    helper::git(&dir, &["checkout", "-q", "--orphan", "gh-pages"]);
+    fs::write(dir.join("CNAME"), "pages.example.io\n").unwrap();
+    helper::git(&dir, &["add", "CNAME"]);
+    helper::commit(&dir, "publish");
+    helper::git(&dir, &["checkout", "-q", "-f", "master"]);
+    assert_eq!(
+        find_local_website(&c),
+        Some("https://pages.example.io".to_string()),
+    );
+    assert_eq!(
+        build_page_url(
+            &Page::Open {
+                website: true,
+                pull_request: false,
+            },
+            &c,
+        )
+        .unwrap(),
+        "https://pages.example.io",
+    );
+}
+
+#[test]
+fn local_website_ignored_for_other_repo() {
+    let dir = helper::init_temp_repo("website-other-repo");
+    helper::commit(&dir, "initial commit");
+    fs::write(dir.join("CNAME"), "docs.example.io\n").unwrap();
+
+    let page = Page::Open {
+        website: true,
+        pull_request: false,
+    };
+
+    // Repository specified with --repo is not related to the current directory
+    let c = config(dir.clone(), None, false);
+    assert_ne!(
+        build_page_url(&page, &c).unwrap(),
+        "https://docs.example.io"
+    );
+
+    // Remote specified with --remote may be another repository such as upstream of fork
+    let c = config(dir, Some("upstream"), false);
+    assert_ne!(
+        build_page_url(&page, &c).unwrap(),
+        "https://docs.example.io"
+    );
+}
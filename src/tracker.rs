@@ -0,0 +1,67 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::git::Git;
+use regex::Regex;
+
+// External issue tracker such as Jira, YouTrack or Linear declared in Git config:
+//
+//   [brws "tracker.jira"]
+//     pattern = PLAT-\\d+
+//     url = https://jira.example.com/browse/{key}
+#[derive(Debug)]
+pub struct Tracker {
+    pattern: Regex,
+    url: String,
+}
+
+impl Tracker {
+    pub fn issue_url(&self, key: &str) -> Option<String> {
+        if self.pattern.is_match(key) {
+            Some(self.url.replace("{key}", key))
+        } else {
+            None
+        }
+    }
+}
+
+fn config_error(name: &str, msg: impl ToString) -> Box<Error> {
+    Error::new(ErrorKind::TrackerConfigError {
+        name: name.to_string(),
+        msg: msg.to_string(),
+    })
+}
+
+// Trackers are returned in the order of declarations in Git config
+pub fn load_trackers(git: &Git) -> Result<Vec<Tracker>> {
+    let mut decls: Vec<(String, Option<String>, Option<String>)> = vec![];
+    for (key, value) in git.config_entries(r"^brws\.tracker\..+\.(pattern|url)$")? {
+        let (name, var) = match key["brws.tracker.".len()..].rsplit_once('.') {
+            Some(split) => split,
+            None => continue,
+        };
+        let idx = match decls.iter().position(|(n, _, _)| n == name) {
+            Some(idx) => idx,
+            None => {
+                decls.push((name.to_string(), None, None));
+                decls.len() - 1
+            }
+        };
+        if var == "pattern" {
+            decls[idx].1 = Some(value);
+        } else {
+            decls[idx].2 = Some(value);
+        }
+    }
+
+    decls
+        .into_iter()
+        .map(|(name, pattern, url)| {
+            let pattern = pattern.ok_or_else(|| config_error(&name, "'pattern' is not set"))?;
+            let url = url.ok_or_else(|| config_error(&name, "'url' is not set"))?;
+            // Note: Whole key must match the pattern
+            let pattern = Regex::new(&format!("^(?:{})$", pattern)).map_err(|e| {
+                config_error(&name, format!("Invalid pattern '{}': {}", pattern, e))
+            })?;
+            Ok(Tracker { pattern, url })
+        })
+        .collect()
+}
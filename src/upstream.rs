@@ -0,0 +1,117 @@
+use crate::async_runtime;
+use crate::config::EnvConfig;
+use crate::error::{Error, ErrorKind, Result};
+use crate::git::Git;
+use crate::github_api::Client;
+use crate::service::{parse_repo_url, slug_from_path};
+
+// Fork whose pages are retargeted at its upstream repository with --upstream
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fork {
+    pub user: String,
+    pub repo: String,
+    // None when the default branch of the upstream cannot be known
+    pub upstream_default_branch: Option<String>,
+}
+
+// Upstream repository which pages of the fork are retargeted at
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upstream {
+    pub url: String,
+    // Local remote tracking the upstream repository if any
+    pub remote: Option<String>,
+    pub fork: Fork,
+}
+
+// Only GitHub provides parent of a fork via API
+fn github_api_endpoint(host: &str, env: &EnvConfig) -> Option<String> {
+    if host == "github.com" {
+        return Some("api.github.com".to_string());
+    }
+    match env.ghe_url_host {
+        Some(ref h) if h == host => Some(format!("{}/api/v3", host)),
+        _ if host.starts_with("github.") => Some(format!("{}/api/v3", host)),
+        _ => None,
+    }
+}
+
+fn parent_via_api(
+    host: &str,
+    user: &str,
+    repo: &str,
+    env: &EnvConfig,
+) -> Option<(String, Option<String>)> {
+    let endpoint = github_api_endpoint(host, env)?;
+    let client = Client::from_env(&endpoint, env).ok()?;
+    let parent = async_runtime::blocking(client.repo(user, repo))
+        .ok()?
+        .parent?;
+    Some((
+        format!(
+            "https://{}/{}/{}.git",
+            host, parent.owner.login, parent.name
+        ),
+        parent.default_branch,
+    ))
+}
+
+// Compares only the last two components of path since remote URL may be in scp-like syntax
+fn same_repo(url1: &str, url2: &str) -> bool {
+    fn slug(url: &str) -> Vec<&str> {
+        let mut slug = url
+            .trim_end_matches('/')
+            .trim_end_matches(".git")
+            .rsplit(&['/', ':'][..])
+            .take(2)
+            .collect::<Vec<_>>();
+        slug.reverse();
+        slug
+    }
+    slug(url1) == slug(url2)
+}
+
+// Note: Remote named 'upstream' is the convention to track the original repository of a fork
+fn parent_via_remote(git: &Git) -> Option<(String, Option<String>)> {
+    let url = git.remote_url("upstream").ok()?;
+    let default_branch = git
+        .remote_default_branch("upstream")
+        .ok()
+        .map(|b| b.trim_start_matches("upstream/").to_string());
+    Some((url, default_branch))
+}
+
+// Resolves the upstream repository of the fork through API, or through 'upstream' remote when the
+// API is not available (e.g. offline)
+pub fn resolve(repo_url: &str, git: &Git, env: &EnvConfig) -> Result<Upstream> {
+    let url = parse_repo_url(repo_url)?;
+    let host = url.host_str().ok_or_else(|| {
+        Error::new(ErrorKind::BrokenUrl {
+            url: repo_url.to_string(),
+            msg: "No host in URL".to_string(),
+        })
+    })?;
+    let (user, repo) = slug_from_path(url.path())?;
+
+    let (upstream_url, upstream_default_branch) = parent_via_api(host, user, repo, env)
+        .or_else(|| parent_via_remote(git))
+        .ok_or_else(|| {
+            Error::new(ErrorKind::UpstreamNotFound {
+                repo: repo_url.to_string(),
+            })
+        })?;
+
+    let remote = match git.remote_url("upstream") {
+        Ok(url) if same_repo(&url, &upstream_url) => Some("upstream".to_string()),
+        _ => None,
+    };
+
+    Ok(Upstream {
+        url: upstream_url,
+        remote,
+        fork: Fork {
+            user: user.to_string(),
+            repo: repo.to_string(),
+            upstream_default_branch,
+        },
+    })
+}
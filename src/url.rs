@@ -1,5 +1,6 @@
 use crate::config::{Config, EnvConfig};
 use crate::error::{Error, ErrorKind, Result};
+use crate::issue_ref;
 use crate::page::parse_page;
 use crate::service;
 use std::process::{Command, ExitStatus, Stdio};
@@ -9,6 +10,21 @@ pub fn build_url(cfg: &Config) -> Result<String> {
     service::build_page_url(&page, &cfg)
 }
 
+// Some options such as --issues may open multiple pages at once. Warnings to be reported to users
+// are pushed to `warnings`
+pub fn build_urls(cfg: &Config, warnings: &mut Vec<String>) -> Result<Vec<String>> {
+    if !cfg.issues {
+        let page = parse_page(cfg)?;
+        return Ok(vec![service::build_page_url_with_warnings(
+            &page, cfg, warnings,
+        )?]);
+    }
+    issue_ref::parse_pages(cfg)?
+        .iter()
+        .map(|page| service::build_page_url(page, cfg))
+        .collect()
+}
+
 fn browse_with_cmd(url: &str, cmd: &str) -> Result<()> {
     let out = Command::new(cmd)
         .arg(url)
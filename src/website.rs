@@ -0,0 +1,100 @@
+use crate::config::Config;
+use crate::package::toml_value;
+use std::fs;
+use std::path::Path;
+
+// Placeholder URLs generated by site generators are not actual websites
+fn website_url(value: &str) -> Option<String> {
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    let url = if value.starts_with("https://") || value.starts_with("http://") {
+        value.to_string()
+    } else if !value.is_empty() && !value.contains('/') && value.contains('.') {
+        // CNAME file contains only a domain
+        format!("https://{}", value)
+    } else {
+        return None;
+    };
+    let host = url.split("://").nth(1)?.split('/').next()?;
+    if host.is_empty() || host == "example.org" || host == "example.com" || host == "localhost" {
+        return None;
+    }
+    Some(url)
+}
+
+fn read(root: &Path, file: &str) -> Option<String> {
+    fs::read_to_string(root.join(file)).ok()
+}
+
+fn cname_on_gh_pages(cfg: &Config) -> Option<String> {
+    let git = cfg.git();
+    let mut branches = vec!["gh-pages".to_string()];
+    if let Some(ref remote) = cfg.remote {
+        branches.insert(0, format!("{}/gh-pages", remote));
+    }
+    branches
+        .iter()
+        .find_map(|b| git.file_content(b, "CNAME").ok())
+        .and_then(|c| c.lines().find_map(website_url))
+}
+
+fn cname_file(root: &Path) -> Option<String> {
+    ["docs/CNAME", "CNAME"]
+        .iter()
+        .filter_map(|f| read(root, f))
+        .find_map(|c| c.lines().find_map(website_url))
+}
+
+fn manifest_homepage(root: &Path) -> Option<String> {
+    if let Some(content) = read(root, "Cargo.toml") {
+        if let Some(url) = toml_value(&content, &["[package]"], "homepage") {
+            if let Some(url) = website_url(&url) {
+                return Some(url);
+            }
+        }
+    }
+    let content = read(root, "package.json")?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json["homepage"].as_str().and_then(website_url)
+}
+
+fn yaml_value(content: &str, key: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let value = line.strip_prefix(key)?.trim_start().strip_prefix(':')?;
+        website_url(value)
+    })
+}
+
+fn mkdocs_site_url(root: &Path) -> Option<String> {
+    yaml_value(&read(root, "mkdocs.yml")?, "site_url")
+}
+
+fn hugo_base_url(root: &Path) -> Option<String> {
+    for file in &["hugo.toml", "config.toml"] {
+        if let Some(content) = read(root, file) {
+            if let Some(url) = toml_value(&content, &[""], "baseURL") {
+                if let Some(url) = website_url(&url) {
+                    return Some(url);
+                }
+            }
+        }
+    }
+    ["hugo.yaml", "config.yaml"]
+        .iter()
+        .filter_map(|f| read(root, f))
+        .find_map(|c| yaml_value(&c, "baseURL"))
+}
+
+// Website declared in the local repository. Sources are looked up in the following order:
+//   1. CNAME file on gh-pages branch
+//   2. CNAME file in docs/ directory or at the root
+//   3. 'homepage' in Cargo.toml or package.json
+//   4. 'site_url' in mkdocs.yml
+//   5. 'baseURL' in Hugo config
+pub fn find_local_website(cfg: &Config) -> Option<String> {
+    let root = cfg.git().root_dir().ok()?;
+    cname_on_gh_pages(cfg)
+        .or_else(|| cname_file(&root))
+        .or_else(|| manifest_homepage(&root))
+        .or_else(|| mkdocs_site_url(&root))
+        .or_else(|| hugo_base_url(&root))
+}